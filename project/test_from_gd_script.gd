@@ -6,9 +6,27 @@ func _enter_tree() -> void:
 	var coroutine: SpireCoroutine = test_routine()
 	var ret = await coroutine.finished
 	print("Result from GDScript: " + str(ret))
+	if ret != 5:
+		report_failure("Expected 5 from awaiting `finished`, got: " + str(ret))
+	
+	var self_coroutine: SpireCoroutine = test_from_self()
+	var self_ret = await self_coroutine.finished
+	print("Result from self: " + str(self_ret))
 	
 	var other = Node.new()
 	add_child(other)
 	var other_coroutine = test_from_other_node(other)
 	var other_ret = await other_coroutine.finished
 	print("Result from Other: " + str(other_ret))
+	
+	var typed_coroutine: SpireCoroutine = test_typed_result()
+	var typed_ret = await typed_coroutine.finished
+	print("Typed result: " + str(typed_ret))
+	if typeof(typed_ret) != TYPE_VECTOR2 or typed_ret != Vector2(1, 2):
+		report_failure("Expected Vector2(1, 2) from awaiting `finished`, got: " + str(typed_ret))
+	
+	var early_coroutine: SpireCoroutine = test_finish_with()
+	var early_ret = await early_coroutine.finished
+	print("Result from finish_with: " + str(early_ret))
+	if early_ret != "finished early":
+		report_failure("Expected `finished early` from awaiting `finished`, got: " + str(early_ret))
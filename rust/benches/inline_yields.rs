@@ -0,0 +1,82 @@
+//! Compares heap allocations and time spent creating/polling condition yields,
+//! as happens when a coroutine re-yields a condition every frame.
+//!
+//! `boxed` is the [Yield::Dyn] path every condition used to take, `inline` is the [Yield::Inline] path used by [wait_while].
+//!
+//! Run with `cargo bench --bench inline_yields`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use gdext_coroutines::prelude::*;
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		System.alloc(layout)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout)
+	}
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn boxed(i: usize, target: usize) -> Yield {
+	Yield::Dyn(Box::new(move || i < target))
+}
+
+fn inline(i: usize, target: usize) -> Yield {
+	wait_while(move || i < target)
+}
+
+fn create_and_poll(make_yield: fn(usize, usize) -> Yield, i: usize) -> bool {
+	let mut yield_ = make_yield(black_box(i), black_box(500_000));
+	let waiter = yield_.as_dyn_mut().unwrap();
+	waiter.keep_waiting(0.016)
+}
+
+fn report_allocations(name: &str, make_yield: fn(usize, usize) -> Yield) {
+	const ITERATIONS: usize = 10_000;
+
+	let allocations_before = ALLOCATIONS.load(Ordering::Relaxed);
+
+	for i in 0..ITERATIONS {
+		black_box(create_and_poll(make_yield, i));
+	}
+
+	let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations_before;
+	println!("condition_yield/{name}: {:.2} allocations/yield", allocations as f64 / ITERATIONS as f64);
+}
+
+fn condition_yield(c: &mut Criterion) {
+	report_allocations("boxed", boxed);
+	report_allocations("inline", inline);
+
+	let mut group = c.benchmark_group("condition_yield");
+
+	let mut i = 0;
+	group.bench_function("boxed", |b| b.iter(|| {
+		i += 1;
+		create_and_poll(boxed, i)
+	}));
+
+	let mut i = 0;
+	group.bench_function("inline", |b| b.iter(|| {
+		i += 1;
+		create_and_poll(inline, i)
+	}));
+
+	group.finish();
+}
+
+criterion_group!(benches, condition_yield);
+criterion_main!(benches);
@@ -3,9 +3,15 @@
 #![feature(stmt_expr_attributes)]
 #![feature(unboxed_closures)]
 
+use std::cell::{Cell, RefCell};
+use std::ops::Coroutine;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use async_compat::Compat;
 use gdext_coroutines::prelude::*;
+use godot::classes::{Control, Engine};
 use godot::classes::node::ProcessMode;
 use godot::obj::WithBaseField;
 use godot::prelude::*;
@@ -34,6 +40,49 @@ impl TestClass {
 			}))
 	}
 	
+	#[func]
+	fn test_from_self(&self) -> Gd<SpireCoroutine> {
+		self.start_coroutine(
+			#[coroutine] || {
+				yield frames(1);
+				"started from &self"
+			})
+	}
+
+	/// Lets the GDScript tests report failures through [TestReporter].
+	#[func]
+	fn report_failure(&self, msg: GString) {
+		log_err(msg);
+	}
+
+	#[func]
+	fn test_typed_result(&self) -> Gd<SpireCoroutine> {
+		self.start_coroutine(
+			#[coroutine] || {
+				yield frames(2);
+				Vector2::new(1.0, 2.0)
+			})
+	}
+
+	#[func]
+	fn test_finish_with(&self) -> Gd<SpireCoroutine> {
+		let coroutine =
+			self.start_coroutine(
+				#[coroutine] || {
+					yield frames(1000);
+				});
+
+		let mut finish_early = coroutine.clone();
+		let mut timer = self.base().get_tree().unwrap().create_timer(0.1).unwrap();
+		timer.connect("timeout", &Callable::from_local_fn("finish_early",
+			move |_args| {
+				finish_early.bind_mut().finish_with("finished early".to_variant());
+				Ok(Variant::nil())
+			}));
+
+		coroutine
+	}
+
 	#[func]
 	fn test_from_other_node(node: Gd<Node>) -> Gd<SpireCoroutine> {
 		node.start_async_task(Compat::new(
@@ -54,14 +103,91 @@ impl INode for TestClass {
 	}
 }
 
+/// Spawns coroutines from its own `ready`, used by test 19.
+#[derive(GodotClass)]
+#[class(init, base = Node)]
+struct ReadySpawner {
+	base: Base<Node>,
+	ready_frame: u64,
+	deferred_first_step: Rc<Cell<Option<u64>>>,
+	immediate_ran: Rc<Cell<bool>>,
+	immediate_ran_before_return: bool,
+}
+
+#[godot_api]
+impl INode for ReadySpawner {
+	fn ready(&mut self) {
+		self.ready_frame = Engine::singleton().get_process_frames();
+
+		let deferred_first_step = self.deferred_first_step.clone();
+		self.start_coroutine(
+			#[coroutine] move || {
+				deferred_first_step.set(Some(Engine::singleton().get_process_frames()));
+				yield frames(1);
+			});
+
+		let immediate_ran = self.immediate_ran.clone();
+		self.coroutine(
+			#[coroutine] move || {
+				immediate_ran.set(true);
+				yield frames(1);
+			})
+			.start_mode(CoroutineStartMode::Immediate)
+			.spawn();
+
+		self.immediate_ran_before_return = self.immediate_ran.get();
+	}
+}
+
 fn log(msg: impl std::fmt::Display) {
 	godot_print!("[{:.6}] {msg}", godot::classes::Engine::singleton().get_process_frames());
 }
 
 fn log_err(msg: impl std::fmt::Display) {
+	TestReporter::record_failure();
 	godot_print_rich!("[color=red]ERROR[/color]: [{:.6}] {msg}", godot::classes::Engine::singleton().get_process_frames());
 }
 
+thread_local! {
+	static FAILURES: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Collects the failures reported through [log_err], so that the outcome of the test sequence can be checked by CI.
+///
+/// When the project is launched with `-- --ci`, the engine quits once the sequence ends, 
+/// with exit code 1 if any test failed.
+struct TestReporter;
+
+impl TestReporter {
+	fn record_failure() {
+		FAILURES.set(FAILURES.get() + 1);
+	}
+
+	/// Prints a machine-readable summary, then quits if running in CI mode.
+	fn finish() {
+		let failures = FAILURES.get();
+
+		if failures == 0 {
+			godot_print!("TEST RESULT: ok");
+		} else {
+			godot_print_rich!("[color=red]TEST RESULT: FAILED[/color] ({failures} failures)");
+		}
+
+		let ci = godot::classes::Os::singleton()
+			.get_cmdline_user_args()
+			.as_slice()
+			.contains(&GString::from("--ci"));
+
+		if ci {
+			let tree = Engine::singleton().get_main_loop().and_then(|main_loop| main_loop.try_cast::<SceneTree>().ok());
+
+			if let Some(mut tree) = tree {
+				tree.quit_ex().exit_code(if failures == 0 { 0 } else { 1 }).done();
+			}
+		}
+	}
+}
+
 fn test_1(node: Gd<Node>) {
 	log("Starting test 1");
 
@@ -406,4 +532,4865 @@ fn test_5(node: Gd<Node>) {
 	    .spawn()
 	    .bind_mut()
 	    .finish_with(5_i32.to_variant());
-}
\ No newline at end of file
+
+	test_6(node);
+}
+
+fn test_6(node: Gd<Node>) {
+	log("Starting test 6");
+
+	let process_routine = node.start_coroutine(seconds_accuracy_routine("Process"));
+
+	let physics_routine =
+		node.coroutine(seconds_accuracy_routine("Physics"))
+		    .poll_mode(PollMode::Physics)
+		    .spawn();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield process_routine.wait_until_finished();
+			yield physics_routine.wait_until_finished();
+
+			log("Test 6 finished");
+
+			test_7(node_ref);
+		});
+}
+
+fn test_7(node: Gd<Node>) {
+	log("Starting test 7");
+
+	let mut emitter = Node::new_alloc();
+	emitter.add_user_signal("test_signal");
+	node.clone().add_child(&emitter);
+
+	let resumed = Rc::new(Cell::new(false));
+
+	let mut killed_routine = {
+		let emitter = emitter.clone();
+		let resumed = resumed.clone();
+
+		node.start_coroutine(
+			#[coroutine] move || {
+				let awaiter = SignalAwaiter::new(emitter, "test_signal");
+				yield awaiter.yield_wait();
+				resumed.set(true);
+			})
+	};
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			let awaiter = SignalAwaiter::new(emitter.clone(), "test_signal");
+
+			// Give the other routine a chance to connect before killing it.
+			yield frames(1);
+
+			killed_routine.bind_mut().kill();
+
+			// The killed routine's closure is only dropped once its node is actually freed.
+			yield frames(1);
+
+			let connections = emitter.get_signal_connection_list("test_signal").len();
+			if connections != 1 {
+				log_err(format!("Expected only 1 connection after killing the awaiting routine, got: {connections}"));
+			}
+
+			emitter.emit_signal("test_signal", &[5.to_variant()]);
+			emitter.emit_signal("test_signal", &[6.to_variant()]);
+
+			if resumed.get() {
+				log_err("Killed routine resumed after the signal was emitted");
+			}
+
+			if awaiter.args() != Some(vec![5.to_variant()]) {
+				log_err(format!("Expected awaiter to receive the first emission's arguments, got: {:?}", awaiter.args()));
+			}
+
+			let connections = emitter.get_signal_connection_list("test_signal").len();
+			if connections != 0 {
+				log_err(format!("Expected no connections after the signal was emitted, got: {connections}"));
+			}
+
+			yield awaiter.yield_wait();
+
+			emitter.queue_free();
+
+			log("Test 7 finished");
+
+			test_8(node_ref);
+		});
+}
+
+fn test_8(node: Gd<Node>) {
+	log("Starting test 8");
+
+	let coroutine =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(5);
+				yield seconds(0.5);
+				"blocked"
+			});
+
+	let result = block_until_finished(coroutine, 100);
+	if result != Some("blocked".to_variant()) {
+		log_err(format!("Expected block_until_finished to return `blocked`, got: {result:?}"));
+	}
+
+	let mut endless =
+		node.start_coroutine(
+			#[coroutine] || {
+				loop {
+					yield frames(1);
+				}
+				#[allow(unreachable_code)]
+				()
+			});
+
+	let result = block_until_finished(endless.clone(), 100);
+	if result.is_some() {
+		log_err(format!("Expected block_until_finished to give up on endless coroutine, got: {result:?}"));
+	}
+
+	endless.bind_mut().kill();
+
+	log("Test 8 finished");
+
+	test_9(node);
+}
+
+fn test_9(node: Gd<Node>) {
+	log("Starting test 9");
+
+	let mut self_pausing =
+		node.start_coroutine(
+			#[coroutine] || {
+				let Some(mut me) = SpireCoroutine::current()
+				else {
+					log_err("SpireCoroutine::current() returned None inside the coroutine's closure");
+					return;
+				};
+
+				me.set_name("SelfPausing");
+
+				me.bind_mut().pause();
+				yield frames(1);
+
+				log("Self-paused routine was resumed");
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(2);
+
+			if !self_pausing.is_paused() {
+				log_err("Expected self-pausing routine to be paused");
+			}
+
+			if self_pausing.get_name() != "SelfPausing".into() {
+				log_err(format!("Expected self-pausing routine to be renamed, got: {}", self_pausing.get_name()));
+			}
+
+			self_pausing.bind_mut().resume();
+			yield self_pausing.wait_until_finished();
+
+			if SpireCoroutine::current().is_none() {
+				log_err("SpireCoroutine::current() returned None inside the coroutine's closure");
+			}
+
+			log("Test 9 finished");
+
+			test_10(node_ref);
+		});
+}
+
+fn test_10(node: Gd<Node>) {
+	log("Starting test 10");
+
+	let resumed_after_kill = Rc::new(Cell::new(false));
+
+	let mut self_killing = {
+		let resumed_after_kill = resumed_after_kill.clone();
+
+		node.start_coroutine(
+			#[coroutine] move || {
+				yield frames(1);
+
+				let mut me = SpireCoroutine::current().unwrap();
+				me.bind_mut().kill();
+
+				// `frames(0)` is immediately satisfied, a killed coroutine must not be resumed in the same poll.
+				yield frames(0);
+
+				resumed_after_kill.set(true);
+				"should not finish"
+			})
+	};
+
+	self_killing.connect(SIGNAL_FINISHED, &Callable::from_local_fn("self_killing_finished",
+		|_args| {
+			log_err("Self-killing routine emitted `finished`");
+			Ok(Variant::nil())
+		}));
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(3);
+
+			if resumed_after_kill.get() {
+				log_err("Self-killing routine was resumed after killing itself");
+			}
+
+			if !self_killing.is_finished() {
+				log_err("Self-killing routine not finished after killing itself");
+			}
+
+			log("Test 10 finished");
+
+			test_11(node_ref);
+		});
+}
+
+
+fn test_11(node: Gd<Node>) {
+	log("Starting test 11");
+
+	const COUNT: usize = 500;
+
+	let pool = CoroutinePool::new(COUNT);
+
+	let owner = node.clone();
+	let spawn_batch = move |pool: Option<&CoroutinePool>| -> (Vec<Gd<SpireCoroutine>>, u64) {
+		let time = godot::classes::Time::singleton();
+		let start_usec = time.get_ticks_usec();
+
+		let spawned =
+			(0..COUNT)
+				.map(|_| {
+					let builder =
+						owner.coroutine(
+							#[coroutine] || {
+								yield frames(1);
+							});
+
+					match pool {
+						Some(pool) => builder.pool(pool).spawn(),
+						None => builder.spawn(),
+					}
+				})
+				.collect::<Vec<_>>();
+
+		(spawned, time.get_ticks_usec() - start_usec)
+	};
+
+	let (_, unpooled_usec) = spawn_batch(None);
+	let (first_batch, _) = spawn_batch(Some(&pool));
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(3);
+
+			if pool.idle_count() != COUNT {
+				log_err(format!("Expected {COUNT} idle coroutines in the pool, got: {}", pool.idle_count()));
+			}
+
+			let first = first_batch[0].clone();
+			let waiting_on_stale = first.wait_until_finished();
+
+			let (second_batch, pooled_usec) = spawn_batch(Some(&pool));
+
+			log(format!("Spawning {COUNT} coroutines: {unpooled_usec}us without pool, {pooled_usec}us with pool"));
+
+			if pool.idle_count() != 0 {
+				log_err(format!("Expected pool to be drained, got: {} idle coroutines", pool.idle_count()));
+			}
+
+			if !second_batch.contains(&first) {
+				log_err("Pooled nodes were not reused");
+			}
+
+			if first.bind().get_generation() != 1 {
+				log_err(format!("Expected generation 1, got: {}", first.bind().get_generation()));
+			}
+
+			// Waiting on a handle whose node was reused must not wait for the new coroutine.
+			yield waiting_on_stale;
+
+			if first.is_finished() {
+				log_err("Reused coroutine finished too early");
+			}
+
+			log("Test 11 finished");
+
+			test_12(node_ref);
+		});
+}
+
+
+fn test_12(node: Gd<Node>) {
+	log("Starting test 12");
+
+	node.coroutine(
+		#[coroutine] || {
+			let engine = Engine::singleton();
+
+			for _ in 0..5 {
+				let process_frames = engine.get_process_frames();
+				yield wait_for_next_process_phase();
+
+				if engine.get_process_frames() == process_frames {
+					log_err("Physics routine resumed before a new process frame started");
+				}
+
+				let physics_frames = engine.get_physics_frames();
+				yield wait_for_next_physics_phase();
+
+				if engine.get_physics_frames() == physics_frames {
+					log_err("Physics routine resumed before a new physics tick started");
+				}
+			}
+		})
+		.poll_mode(PollMode::Physics)
+		.spawn();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] || {
+			let engine = Engine::singleton();
+
+			for _ in 0..5 {
+				let physics_frames = engine.get_physics_frames();
+				yield wait_for_next_physics_phase();
+
+				if engine.get_physics_frames() == physics_frames {
+					log_err("Process routine resumed before a new physics tick started");
+				}
+			}
+
+			log("Test 12 finished");
+
+			test_13(node_ref);
+		});
+}
+
+
+fn test_13(node: Gd<Node>) {
+	log("Starting test 13");
+
+	let template =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(1);
+			})
+			.poll_mode(PollMode::Physics)
+			.auto_start(false)
+			.on_finish(|_| log_err("Discarded callback was invoked"));
+
+	let mut rebound =
+		template
+			.rebind(
+				#[coroutine] || {
+					yield frames(1);
+					7
+				}, false)
+			.on_finish(|result: i32| {
+				if result != 7 {
+					log_err(format!("Expected rebound result 7, got: {result}"));
+				}
+			})
+			.spawn();
+
+	if !rebound.is_paused() {
+		log_err("Rebound coroutine did not keep `auto_start(false)`");
+	}
+
+	rebound.bind_mut().resume();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield rebound.wait_until_finished();
+
+			log("Test 13 finished");
+
+			test_14(node_ref);
+		});
+}
+
+
+fn test_14(node: Gd<Node>) {
+	log("Starting test 14");
+
+	let time = godot::classes::Time::singleton();
+
+	let physics_start_usec = time.get_ticks_usec();
+	let physics_seconds =
+		node.coroutine(
+			#[coroutine] move || {
+				yield seconds(1.0);
+
+				let elapsed = (godot::classes::Time::singleton().get_ticks_usec() - physics_start_usec) as f64 / 1_000_000.0;
+				log(format!("Physics poll mode: `seconds(1.0)` took {elapsed:.4}s"));
+
+				// At default settings, the engine keeps up with the physics tick rate, so physics time matches real time.
+				if (elapsed - 1.0).abs() > 0.1 {
+					log_err(format!("Expected `seconds(1.0)` under physics poll mode to take ~1s, took: {elapsed:.4}s"));
+				}
+			})
+			.poll_mode(PollMode::Physics)
+			.spawn();
+
+	let real_start_usec = time.get_ticks_usec();
+	let mut real_seconds_paused =
+		node.start_coroutine(
+			#[coroutine] move || {
+				yield real_seconds(0.5);
+
+				let elapsed = (godot::classes::Time::singleton().get_ticks_usec() - real_start_usec) as f64 / 1_000_000.0;
+
+				// Paused for 0.6 seconds right after yielding, so `real_seconds(0.5)` must resume on the first poll after resuming.
+				// Had it stopped counting while paused, it would take at least 1.1 seconds.
+				if elapsed > 0.9 {
+					log_err(format!("Expected `real_seconds` to keep counting while paused, took: {elapsed:.4}s"));
+				}
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			// Spawned earlier, `real_seconds_paused` already yielded (computing its deadline) by the time this runs.
+			yield frames(1);
+			real_seconds_paused.bind_mut().pause();
+
+			yield seconds(0.6);
+			real_seconds_paused.bind_mut().resume();
+
+			yield physics_seconds.wait_until_finished();
+			yield real_seconds_paused.wait_until_finished();
+
+			log("Test 14 finished");
+
+			test_15(node_ref);
+		});
+}
+
+
+fn test_15(node: Gd<Node>) {
+	log("Starting test 15");
+
+	let mut stuck =
+		node.coroutine(
+			#[coroutine] || {
+				yield wait_until(|| false);
+			})
+			.stuck_threshold(5)
+			.spawn();
+
+	let waiting_frames =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(20);
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			if stuck.bind().is_possibly_stuck() {
+				log_err("Coroutine reported as stuck right after spawning");
+			}
+
+			yield frames(10);
+
+			if !stuck.bind().is_possibly_stuck() {
+				log_err("Coroutine waiting on an impossible condition not reported as stuck");
+			}
+
+			if waiting_frames.bind().is_possibly_stuck() {
+				log_err("Coroutine waiting on frames reported as stuck");
+			}
+
+			stuck.bind_mut().kill();
+
+			log("Test 15 finished");
+
+			test_16(node_ref);
+		});
+}
+
+
+fn test_16(node: Gd<Node>) {
+	log("Starting test 16");
+
+	let result = Rc::new(Cell::new(0));
+	let result_ref = result.clone();
+
+	let ready_task = 
+		node.async_task_eager(async { 16 })
+			.on_finish(move |value: i32| result_ref.set(value))
+			.spawn();
+
+	if !ready_task.is_finished() || result.get() != 16 {
+		log_err(format!("Expected a ready eager async task to finish during `spawn`, result: {}", result.get()));
+	}
+
+	let pending_task = node.start_async_task_eager(async { smol::Timer::after(Duration::from_millis(50)).await; });
+
+	if pending_task.is_finished() {
+		log_err("Expected a pending eager async task not to finish during `spawn`");
+	}
+
+	let polled = Arc::new(AtomicBool::new(false));
+
+	let polled_ref = polled.clone();
+	drop(node.async_task_eager(async move { polled_ref.store(true, Ordering::Relaxed); }));
+
+	if polled.load(Ordering::Relaxed) {
+		log_err("Expected a dropped eager async task builder not to poll its future");
+	}
+
+	let polled_ref = polled.clone();
+	let deferred_task =
+		node.async_task_eager(async move { polled_ref.store(true, Ordering::Relaxed); })
+			.start_mode(CoroutineStartMode::Deferred)
+			.spawn();
+
+	if polled.load(Ordering::Relaxed) || deferred_task.is_finished() {
+		log_err("Expected an eager async task spawned with `CoroutineStartMode::Deferred` not to be polled during `spawn`");
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield pending_task.wait_until_finished();
+			yield deferred_task.wait_until_finished();
+
+			if !polled.load(Ordering::Relaxed) {
+				log_err("Expected a deferred eager async task to be polled on its first step");
+			}
+
+			log("Test 16 finished");
+
+			test_17(node_ref);
+		});
+}
+
+
+fn test_17(node: Gd<Node>) {
+	log("Starting test 17");
+
+	let time = godot::classes::Time::singleton();
+	let start_usec = time.get_ticks_usec();
+
+	let scaled =
+		node.coroutine(
+			#[coroutine] || {
+				yield seconds(10.0);
+				yield seconds(10.0);
+			})
+			.map_yield(|_yield| {
+				match _yield {
+					Yield::Seconds(secs) => seconds(secs / 100.0),
+					other => other,
+				}
+			})
+			.spawn();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield scaled.wait_until_finished();
+
+			let elapsed = (godot::classes::Time::singleton().get_ticks_usec() - start_usec) as f64 / 1_000_000.0;
+
+			if elapsed > 1.0 {
+				log_err(format!("Expected mapped yields to take ~0.2s, took: {elapsed:.4}s"));
+			}
+
+			log("Test 17 finished");
+
+			test_18(node_ref);
+		});
+}
+
+
+fn test_18(node: Gd<Node>) {
+	log("Starting test 18");
+
+	let state = Rc::new(Cell::new(0));
+
+	let waiting_for_change = {
+		let state = state.clone();
+
+		node.start_coroutine(
+			#[coroutine] move || {
+				let read_state = state.clone();
+				yield wait_for_change(move || read_state.get());
+
+				if state.get() != 1 {
+					log_err(format!("Expected state to be 1 after change, got: {}", state.get()));
+				}
+			})
+	};
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(5);
+
+			if waiting_for_change.is_finished() {
+				log_err("`wait_for_change` resumed before the value changed");
+			}
+
+			state.set(1);
+			yield waiting_for_change.wait_until_finished();
+
+			log("Test 18 finished");
+
+			test_19(node_ref);
+		});
+}
+
+
+fn test_19(mut node: Gd<Node>) {
+	log("Starting test 19");
+
+	let spawner = ReadySpawner::new_alloc();
+	node.add_child(&spawner);
+
+	if !spawner.bind().immediate_ran_before_return {
+		log_err("Coroutine with `CoroutineStartMode::Immediate` did not run its first step during `spawn`");
+	}
+
+	if spawner.bind().deferred_first_step.get().is_some() {
+		log_err("Coroutine spawned in `ready` ran its first step during `spawn`");
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(2);
+
+			let ready_frame = spawner.bind().ready_frame;
+
+			match spawner.bind().deferred_first_step.get() {
+				Some(first_step_frame) => {
+					if first_step_frame <= ready_frame {
+						log_err(format!("Coroutine spawned in `ready` (frame {ready_frame}) ran its first step on the same frame"));
+					}
+				}
+				None => log_err("Coroutine spawned in `ready` never ran"),
+			}
+
+			spawner.free();
+
+			log("Test 19 finished");
+
+			test_20(node_ref);
+		});
+}
+
+
+fn test_20(node: Gd<Node>) {
+	log("Starting test 20");
+
+	let polls = Rc::new(Cell::new(0));
+	let total_delta = Rc::new(Cell::new(0.0));
+
+	let instrumented = {
+		let polls = polls.clone();
+		let total_delta = total_delta.clone();
+
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(4);
+			})
+			.on_each_frame(move |delta| {
+				polls.set(polls.get() + 1);
+				total_delta.set(total_delta.get() + delta);
+			})
+			.spawn()
+	};
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield instrumented.wait_until_finished();
+
+			// 1 poll to start waiting, 4 polls waiting.
+			if polls.get() != 5 {
+				log_err(format!("Expected `on_each_frame` to run 5 times, ran: {}", polls.get()));
+			}
+
+			if total_delta.get() <= 0.0 {
+				log_err("`on_each_frame` did not receive the poll's delta time");
+			}
+
+			log("Test 20 finished");
+
+			test_21(node_ref);
+		});
+}
+
+
+fn test_21(node: Gd<Node>) {
+	log("Starting test 21");
+
+	let completions = Rc::new(std::cell::RefCell::new(Vec::new()));
+
+	let spawned =
+		(0..3)
+			.map(|index| {
+				let completions = completions.clone();
+
+				node.coroutine(
+					#[coroutine] || {
+						yield frames(2);
+					})
+					.on_finish(move |_| completions.borrow_mut().push(index))
+					.spawn()
+			})
+			.collect::<Vec<_>>();
+
+	let sequences = spawned.iter().map(|coroutine| coroutine.bind().get_spawn_sequence()).collect::<Vec<_>>();
+
+	if !sequences.is_sorted() || sequences.windows(2).any(|pair| pair[0] == pair[1]) {
+		log_err(format!("Expected strictly increasing spawn sequences, got: {sequences:?}"));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			for coroutine in spawned {
+				yield coroutine.wait_until_finished();
+			}
+
+			if *completions.borrow() != [0, 1, 2] {
+				log_err(format!("Expected completions in spawn order, got: {:?}", completions.borrow()));
+			}
+
+			log("Test 21 finished");
+
+			test_22(node_ref);
+		});
+}
+
+
+fn test_22(node: Gd<Node>) {
+	log("Starting test 22");
+
+	let condition = Rc::new(Cell::new(true));
+	let time = godot::classes::Time::singleton();
+	let start_usec = time.get_ticks_usec();
+
+	let debounced = {
+		let condition = condition.clone();
+
+		node.start_coroutine(
+			#[coroutine] move || {
+				yield wait_until_stable(0.3, move || condition.get());
+
+				let elapsed = (godot::classes::Time::singleton().get_ticks_usec() - start_usec) as f64 / 1_000_000.0;
+
+				// The condition flickers false after 0.2 seconds, so the timer restarts from there.
+				if elapsed < 0.45 {
+					log_err(format!("Expected `wait_until_stable` to restart after the condition flickered, resumed after: {elapsed:.4}s"));
+				}
+			})
+	};
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield seconds(0.2);
+			condition.set(false);
+			yield frames(1);
+			condition.set(true);
+
+			yield debounced.wait_until_finished();
+
+			log("Test 22 finished");
+
+			test_23(node_ref);
+		});
+}
+
+
+fn test_23(node: Gd<Node>) {
+	log("Starting test 23");
+
+	let mut physics =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(1);
+			})
+			.poll_mode(PollMode::Physics)
+			.process_mode(ProcessMode::ALWAYS)
+			.spawn();
+
+	if physics.bind().get_poll_mode() != PollMode::Physics || !physics.bind().get_poll_mode_is_physics() {
+		log_err("Expected coroutine to report `PollMode::Physics`");
+	}
+
+	if physics.get_process_mode() != ProcessMode::ALWAYS {
+		log_err(format!("Expected coroutine to report `ProcessMode::ALWAYS`, got: {:?}", physics.get_process_mode()));
+	}
+
+	physics.bind_mut().kill();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			if SpireCoroutine::current().is_some_and(|current| current.bind().get_poll_mode_is_physics()) {
+				log_err("Expected default coroutine to report `PollMode::Process`");
+			}
+
+			yield frames(1);
+
+			log("Test 23 finished");
+
+			test_24(node_ref);
+		});
+}
+
+
+fn test_24(node: Gd<Node>) {
+	log("Starting test 24");
+
+	let condition_checks = Rc::new(Cell::new(0));
+
+	let mut fast_forwarded = {
+		let condition_checks = condition_checks.clone();
+
+		node.coroutine(
+			#[coroutine] move || {
+				yield wait_while(move || {
+					condition_checks.set(condition_checks.get() + 1);
+					condition_checks.get() < 5
+				});
+
+				yield seconds(10.0);
+				"fast-forwarded"
+			})
+			.auto_start(false)
+			.spawn()
+	};
+
+	let result = fast_forwarded.bind_mut().advance_time(20.0, 0.5);
+
+	if result != Some("fast-forwarded".to_variant()) {
+		log_err(format!("Expected `advance_time` to finish the coroutine, got: {result:?}"));
+	}
+
+	if condition_checks.get() != 5 {
+		log_err(format!("Expected the condition to be checked 5 times, got: {}", condition_checks.get()));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			log("Test 24 finished");
+
+			test_25(node_ref);
+		});
+}
+
+
+fn test_25(node: Gd<Node>) {
+	log("Starting test 25");
+
+	for _ in 0..100 {
+		match random_seconds(0.5, 1.5) {
+			Yield::Seconds(secs) if (0.5..=1.5).contains(&secs) => {}
+			_ => log_err("`random_seconds` produced a wait outside of its range"),
+		}
+
+		match random_frames(3, 7) {
+			Yield::Frames(frames) if (3..=7).contains(&frames) => {}
+			_ => log_err("`random_frames` produced a wait outside of its range"),
+		}
+	}
+
+	let sample = |seed: u64| -> Vec<i64> {
+		let mut rng = godot::classes::RandomNumberGenerator::new_gd();
+		rng.set_seed(seed);
+
+		(0..10)
+			.map(|_| match random_frames_with(&mut rng, 0, 100) {
+				Yield::Frames(frames) => frames,
+				_ => -1,
+			})
+			.collect()
+	};
+
+	if sample(42) != sample(42) {
+		log_err("`random_frames_with` is not reproducible with the same seed");
+	}
+
+	let mut rng = godot::classes::RandomNumberGenerator::new_gd();
+
+	for (min, max) in [(i64::MIN, i64::MAX), (i64::MAX, i64::MAX - 1), (i64::MIN, i64::MIN)] {
+		match random_frames_with(&mut rng, min, max) {
+			Yield::Frames(frames) if (min.min(max)..=min.max(max)).contains(&frames) => {}
+			_ => log_err(format!("`random_frames_with` produced a wait outside of {min}..={max}")),
+		}
+	}
+
+	for (min, max) in [(i64::MIN, i64::MAX), (i64::MAX, i64::MAX - 1), (i64::MIN, i64::MIN)] {
+		match random_frames(min, max) {
+			Yield::Frames(frames) if (min.min(max)..=min.max(max)).contains(&frames) => {}
+			_ => log_err(format!("`random_frames` produced a wait outside of {min}..={max}")),
+		}
+	}
+
+	// Ranges beyond 32 bits must not be truncated.
+	let beyond_i32 =
+		(0..100).any(|_| matches!(random_frames(0, 5_000_000_000), Yield::Frames(frames) if frames > i32::MAX as i64));
+
+	if !beyond_i32 {
+		log_err("`random_frames` never sampled beyond the 32-bit range of `0..=5_000_000_000`");
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield random_frames(1, 2);
+
+			log("Test 25 finished");
+
+			test_26(node_ref);
+		});
+}
+
+
+fn test_26(node: Gd<Node>) {
+	log("Starting test 26");
+
+	let other_owner = Node::new_alloc();
+	let mut node_mut = node.clone();
+	node_mut.add_child(&other_owner);
+
+	let group = CoroutineGroup::new(node.clone());
+
+	let progress = Rc::new(Cell::new(0));
+
+	for _ in 0..2 {
+		let progress = progress.clone();
+		group.start(
+			#[coroutine] move || {
+				yield frames(2);
+				progress.set(progress.get() + 1);
+			});
+	}
+
+	{
+		let progress = progress.clone();
+		group.add(other_owner.start_coroutine(
+			#[coroutine] move || {
+				yield frames(2);
+				progress.set(progress.get() + 1);
+			}));
+	}
+
+	let killed_group = CoroutineGroup::new(node.clone());
+	let killed =
+		killed_group.start(
+			#[coroutine] || {
+				yield frames(1000);
+			});
+
+	group.pause();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(5);
+
+			if progress.get() != 0 {
+				log_err("Paused group made progress");
+			}
+
+			if group.len() != 3 {
+				log_err(format!("Expected 3 coroutines in the group, got: {}", group.len()));
+			}
+
+			group.resume();
+			yield group.wait_all();
+
+			if progress.get() != 3 {
+				log_err(format!("Expected every coroutine in the group to finish, got: {}", progress.get()));
+			}
+
+			if !group.is_empty() {
+				log_err("Expected group to be empty after every coroutine finished");
+			}
+
+			killed_group.kill();
+			if !killed.is_finished() {
+				log_err("Killing the group did not kill its coroutines");
+			}
+
+			other_owner.free();
+
+			log("Test 26 finished");
+
+			test_27(node_ref);
+		});
+}
+
+
+fn test_27(node: Gd<Node>) {
+	log("Starting test 27");
+
+	let mut skippable =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield seconds(1000.0);
+				yield frames(3);
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(2);
+			skippable.bind_mut().skip_wait();
+
+			// Only the current wait is skipped, `frames(3)` still needs 4 polls after the skip.
+			yield frames(3);
+
+			if skippable.is_finished() {
+				log_err("`skip_wait` skipped more than the current wait");
+			}
+
+			yield skippable.wait_until_finished();
+
+			log("Test 27 finished");
+
+			test_28(node_ref);
+		});
+}
+
+
+fn test_28(node: Gd<Node>) {
+	log("Starting test 28");
+
+	let mut collapsible =
+		node.coroutine(
+			#[coroutine] || {
+				let mut waited = 0;
+
+				for _ in 0..10 {
+					if !SpireCoroutine::is_forcing() {
+						waited += 1;
+						yield frames(1);
+					}
+				}
+
+				waited
+			})
+			.auto_start(false)
+			.spawn();
+
+	let regular =
+		node.start_coroutine(
+			#[coroutine] || {
+				if SpireCoroutine::is_forcing() {
+					log_err("`is_forcing` returned true during a regular poll");
+				}
+
+				yield frames(1);
+			});
+
+	let waited = collapsible.bind_mut().force_run_to_completion();
+
+	if waited != 0.to_variant() {
+		log_err(format!("Expected force-run coroutine to skip every wait, waited: {waited}"));
+	}
+
+	if SpireCoroutine::is_forcing() {
+		log_err("`is_forcing` still true after `force_run_to_completion` returned");
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield regular.wait_until_finished();
+
+			log("Test 28 finished");
+
+			test_29(node_ref);
+		});
+}
+
+
+fn test_29(node: Gd<Node>) {
+	log("Starting test 29");
+
+	let self_referential =
+		node.start_coroutine_pinned(
+			#[coroutine] static || {
+				let values = vec![1, 2, 3];
+				let borrowed = &values;
+
+				yield frames(1);
+
+				borrowed.iter().sum::<i32>()
+			});
+
+	let mut result_check = self_referential.clone();
+	result_check.connect(SIGNAL_FINISHED, &Callable::from_local_fn("check_pinned_result",
+		|args| {
+			if args.first().map(|arg| arg.to::<i32>()) != Some(6) {
+				log_err(format!("Expected pinned coroutine to return 6, got: {args:?}"));
+			}
+
+			Ok(Variant::nil())
+		}));
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield self_referential.wait_until_finished();
+
+			log("Test 29 finished");
+
+			test_30(node_ref);
+		});
+}
+
+
+fn test_30(node: Gd<Node>) {
+	log("Starting test 30");
+
+	let finished_at_tick = Rc::new(Cell::new(None));
+
+	let process_polled = {
+		let finished_at_tick = finished_at_tick.clone();
+
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(3);
+			})
+			.on_finish(move |_| finished_at_tick.set(Some(Engine::singleton().get_physics_frames())))
+			.spawn()
+	};
+
+	let physics_awaiter = {
+		let finished_at_tick = finished_at_tick.clone();
+
+		node.coroutine(
+			#[coroutine] move || {
+				yield process_polled.wait_until_finished();
+
+				let resumed_at_tick = Engine::singleton().get_physics_frames();
+
+				match finished_at_tick.get() {
+					Some(finished_at_tick) => {
+						// Resumes on the first physics tick after the awaited coroutine finished.
+						if resumed_at_tick > finished_at_tick + 1 {
+							log_err(format!("Physics awaiter resumed {} ticks after process coroutine finished",
+								resumed_at_tick - finished_at_tick));
+						}
+					}
+					None => log_err("Physics awaiter resumed before process coroutine finished"),
+				}
+			})
+			.poll_mode(PollMode::Physics)
+			.spawn()
+	};
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield physics_awaiter.wait_until_finished();
+
+			log("Test 30 finished");
+
+			test_31(node_ref);
+		});
+}
+
+
+fn test_31(node: Gd<Node>) {
+	log("Starting test 31");
+
+	let mut effects_root = Node::new_alloc();
+	let mut node_mut = node.clone();
+	node_mut.add_child(&effects_root);
+
+	let coroutine =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(2);
+			})
+			.spawn_on(effects_root.clone());
+
+	if coroutine.get_parent() != Some(effects_root.clone()) {
+		log_err("`spawn_on` did not parent the coroutine to the given node");
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield coroutine.wait_until_finished();
+
+			effects_root.queue_free();
+
+			log("Test 31 finished");
+
+			test_32(node_ref);
+		});
+}
+
+
+struct FailingDownload {
+	polls_until_failure: u32,
+}
+
+impl KeepWaiting for FailingDownload {
+	fn keep_waiting(&mut self, _delta_time: f64) -> bool {
+		true
+	}
+
+	fn poll_wait(&mut self, _delta_time: f64) -> WaitOutcome {
+		if self.polls_until_failure == 0 {
+			WaitOutcome::Abort
+		} else {
+			self.polls_until_failure -= 1;
+			WaitOutcome::Continue
+		}
+	}
+}
+
+fn test_32(node: Gd<Node>) {
+	log("Starting test 32");
+
+	let cancelled = Rc::new(Cell::new(false));
+	let resumed = Rc::new(Cell::new(false));
+
+	let aborted = {
+		let cancelled = cancelled.clone();
+		let resumed = resumed.clone();
+
+		node.coroutine(
+			#[coroutine] move || {
+				yield Yield::Dyn(Box::new(FailingDownload { polls_until_failure: 2 }));
+				resumed.set(true);
+			})
+			.on_finish(|_| log_err("Aborted coroutine emitted `finished`"))
+			.on_cancel(move || cancelled.set(true))
+			.spawn()
+	};
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield aborted.wait_until_finished();
+
+			if resumed.get() {
+				log_err("Aborted coroutine was resumed");
+			}
+
+			if !cancelled.get() {
+				log_err("`on_cancel` was not invoked when the yield aborted");
+			}
+
+			log("Test 32 finished");
+
+			test_33(node_ref);
+		});
+}
+
+
+fn test_33(node: Gd<Node>) {
+	log("Starting test 33");
+
+	let (spawned_before, alive_before) = coroutine_stats();
+
+	let finishing = 
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(1);
+			});
+
+	let mut killed =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(1000);
+			});
+
+	let owner = Node::new_alloc();
+	let mut node_mut = node.clone();
+	node_mut.add_child(&owner);
+	owner.start_coroutine(
+		#[coroutine] || {
+			yield frames(1000);
+		});
+
+	let (spawned, alive) = coroutine_stats();
+
+	if spawned != spawned_before + 3 || alive != alive_before + 3 {
+		log_err(format!("Expected 3 more spawned/alive coroutines, got: ({spawned}, {alive}), before: ({spawned_before}, {alive_before})"));
+	}
+
+	killed.bind_mut().kill();
+	owner.free();
+
+	let (_, alive) = coroutine_stats();
+
+	if alive != alive_before + 1 {
+		log_err(format!("Expected killed and freed coroutines to no longer count as alive, alive: {alive}, before: {alive_before}"));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield finishing.wait_until_finished();
+
+			log("Test 33 finished");
+
+			test_34(node_ref);
+		});
+}
+
+
+fn test_34(node: Gd<Node>) {
+	log("Starting test 34");
+
+	let gate_open = Rc::new(Cell::new(false));
+	let started = Rc::new(Cell::new(false));
+
+	let gated = {
+		let gate_open = gate_open.clone();
+		let started = started.clone();
+
+		node.coroutine(
+			#[coroutine] move || {
+				started.set(true);
+				yield frames(1);
+			})
+			.start_when(move || gate_open.get())
+			.spawn()
+	};
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(3);
+
+			if started.get() || !gated.is_paused() {
+				log_err("Gated coroutine started before its gate opened");
+			}
+
+			gate_open.set(true);
+			yield gated.wait_until_finished();
+
+			if !started.get() {
+				log_err("Gated coroutine never started");
+			}
+
+			log("Test 34 finished");
+
+			test_35(node_ref);
+		});
+}
+
+
+fn test_35(node: Gd<Node>) {
+	log("Starting test 35");
+
+	let engine = Engine::singleton();
+	let expected_ticks = engine.get_physics_ticks_per_second() as u64 / 2;
+	let start_tick = engine.get_physics_frames();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield physics_seconds(0.5);
+
+			let ticks_passed = Engine::singleton().get_physics_frames() - start_tick;
+
+			// Process-polled, so it resumes on the first process frame after the deadline tick.
+			if ticks_passed < expected_ticks || ticks_passed > expected_ticks + 8 {
+				log_err(format!("Expected `physics_seconds(0.5)` to take ~{expected_ticks} physics ticks, took: {ticks_passed}"));
+			}
+
+			log("Test 35 finished");
+
+			test_36(node_ref);
+		});
+}
+
+
+fn test_36(node: Gd<Node>) {
+	log("Starting test 36");
+
+	let mapped_result = Rc::new(Cell::new(0));
+	let mapped_result_clone = mapped_result.clone();
+
+	let mut coroutine =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(1);
+				5
+			})
+			.map_result(|result| (result.to::<i32>() * 10).to_variant())
+			.map_result(|result| (result.to::<i32>() + 1).to_variant())
+			.on_finish(move |result| mapped_result_clone.set(result))
+			.spawn();
+
+	let signal_result = Rc::new(Cell::new(0));
+	let signal_result_clone = signal_result.clone();
+
+	coroutine.connect(SIGNAL_FINISHED, &Callable::from_local_fn("test_36_finished", move |args| {
+		signal_result_clone.set(args[0].to::<i32>());
+		Ok(Variant::nil())
+	}));
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield coroutine.wait_until_finished();
+
+			if mapped_result.get() != 51 {
+				log_err(format!("Expected `on_finish` to receive the mapped result 51, got: {}", mapped_result.get()));
+			}
+
+			if signal_result.get() != 51 {
+				log_err(format!("Expected `finished` to be emitted with the mapped result 51, got: {}", signal_result.get()));
+			}
+
+			log("Test 36 finished");
+
+			test_37(node_ref);
+		});
+}
+
+
+fn test_37(node: Gd<Node>) {
+	log("Starting test 37");
+
+	let mut emitter = Node::new_alloc();
+	emitter.add_user_signal("ok");
+	emitter.add_user_signal("cancel");
+	emitter.add_user_signal("switch");
+	node.clone().add_child(&emitter);
+
+	let (wait_any, fired_any) = wait_for_any_signal([(emitter.clone(), "ok"), (emitter.clone(), "cancel")]);
+	let (wait_all, fired_all) = wait_for_all_signals([(emitter.clone(), "ok"), (emitter.clone(), "switch")]);
+
+	let waiter =
+		node.start_coroutine(
+			#[coroutine] move || {
+				yield wait_any;
+				yield wait_all;
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+			emitter.emit_signal("cancel", &[]);
+			emitter.emit_signal("ok", &[]);
+			yield frames(2);
+
+			match *fired_any.borrow() {
+				Some((1, _)) => {}
+				ref other => log_err(format!("Expected `wait_for_any_signal` to capture `cancel` (index 1), got: {other:?}")),
+			}
+
+			if !emitter.get_signal_connection_list("cancel").is_empty() {
+				log_err("Expected `wait_for_any_signal` to disconnect from every signal after the first emission");
+			}
+
+			emitter.emit_signal("switch", &[5.to_variant()]);
+			yield frames(2);
+
+			if !waiter.is_finished() {
+				log_err("Expected `wait_for_all_signals` to resume after every signal was emitted");
+			}
+
+			if fired_all.borrow()[1] != Some(vec![5.to_variant()]) {
+				log_err(format!("Expected `wait_for_all_signals` to capture `switch`'s arguments, got: {:?}", fired_all.borrow()));
+			}
+
+			let (wait_killed, _) = wait_for_all_signals([(emitter.clone(), "ok")]);
+			let mut killed = emitter.start_coroutine(#[coroutine] move || { yield wait_killed; });
+			yield frames(1);
+			killed.bind_mut().kill();
+			yield frames(1);
+
+			if !emitter.get_signal_connection_list("ok").is_empty() {
+				log_err("Expected killing a coroutine to disconnect the signals it was waiting on");
+			}
+
+			emitter.queue_free();
+			log("Test 37 finished");
+
+			test_38(node_ref);
+		});
+}
+
+
+fn test_38(node: Gd<Node>) {
+	log("Starting test 38");
+
+	let waiter =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(10);
+				yield wait_until(|| false);
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			let mut waiter = waiter;
+
+			let progress = waiter.bind().get_current_wait_progress();
+			if !(0.0..=0.2).contains(&progress) {
+				log_err(format!("Expected progress near 0.0 right after starting to wait, got: {progress}"));
+			}
+
+			yield frames(5);
+
+			let progress = waiter.bind().get_current_wait_progress();
+			if !(0.3..=0.7).contains(&progress) {
+				log_err(format!("Expected progress near 0.5 halfway through the wait, got: {progress}"));
+			}
+
+			yield frames(10);
+
+			let progress = waiter.bind().get_current_wait_progress();
+			if progress != -1.0 {
+				log_err(format!("Expected progress -1.0 while waiting on a custom yield, got: {progress}"));
+			}
+
+			waiter.bind_mut().kill();
+			log("Test 38 finished");
+
+			test_39(node_ref);
+		});
+}
+
+
+fn test_39(node: Gd<Node>) {
+	log("Starting test 39");
+
+	let steps = Rc::new(Cell::new(0));
+	let steps_clone = steps.clone();
+
+	let machine =
+		node.start_coroutine(
+			state_machine(3, move |remaining: &mut i32| {
+				steps_clone.set(steps_clone.get() + 1);
+
+				if *remaining == 0 {
+					Step::Done(7)
+				} else {
+					*remaining -= 1;
+					Step::Wait(frames(1))
+				}
+			}));
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield machine.wait_until_finished();
+
+			if steps.get() != 4 {
+				log_err(format!("Expected the state machine to run 4 steps, ran: {}", steps.get()));
+			}
+
+			log("Test 39 finished");
+
+			test_40(node_ref);
+		});
+}
+
+
+fn test_40(node: Gd<Node>) {
+	log("Starting test 40");
+
+	let forever =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(i64::MAX);
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			let mut forever = forever;
+
+			let start = Engine::singleton().get_process_frames();
+			yield frames(0);
+			yield frames(-5);
+			yield Yield::Frames(-5);
+
+			let passed = Engine::singleton().get_process_frames() - start;
+			if passed != 0 {
+				log_err(format!("Expected `frames(0)` and negative frames to resume in the same poll, {passed} frames passed"));
+			}
+
+			yield frames(3);
+
+			if forever.bind().is_finished() {
+				log_err("Expected `frames(i64::MAX)` to keep waiting");
+			}
+
+			forever.bind_mut().kill();
+			log("Test 40 finished");
+
+			test_41(node_ref);
+		});
+}
+
+
+fn test_41(node: Gd<Node>) {
+	log("Starting test 41");
+
+	let results = Rc::new(RefCell::new(Vec::new()));
+
+	let panicking = {
+		let results = results.clone();
+
+		node.async_task(async {
+				smol::Timer::after(Duration::from_millis(10)).await;
+
+				let should_panic = true;
+				if should_panic {
+					panic!("Test 41: intentional async panic");
+				}
+
+				41
+			})
+			.on_finish_callable(Callable::from_local_fn("test_41_finished", move |args| {
+				results.borrow_mut().push(args[0].clone());
+				Ok(Variant::nil())
+			}))
+			.spawn()
+	};
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield panicking.wait_until_finished();
+
+			if *results.borrow() != vec![Variant::nil()] {
+				log_err(format!("Expected a panicking async task to finish with nil, got: {:?}", results.borrow()));
+			}
+
+			log("Test 41 finished");
+
+			test_42(node_ref);
+		});
+}
+
+
+fn test_42(node: Gd<Node>) {
+	log("Starting test 42");
+
+	let tagged =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(1);
+			})
+			.with_metadata(dict! { "category": "ai", "priority": 1 })
+			.with_metadata(dict! { "priority": 2 })
+			.spawn();
+
+	let metadata = tagged.bind().get_metadata();
+
+	if metadata.get("category") != Some("ai".to_variant()) || metadata.get("priority") != Some(2.to_variant()) {
+		log_err(format!("Expected metadata {{ category: ai, priority: 2 }}, got: {metadata:?}"));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield tagged.wait_until_finished();
+			log("Test 42 finished");
+
+			test_43(node_ref);
+		});
+}
+
+
+fn test_43(node: Gd<Node>) {
+	log("Starting test 43");
+
+	let completed = Arc::new(AtomicBool::new(false));
+
+	let mut task = {
+		let completed = completed.clone();
+
+		node.start_async_task(async move {
+			smol::Timer::after(Duration::from_millis(200)).await;
+			completed.store(true, Ordering::Relaxed);
+		})
+	};
+
+	let routine =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(1);
+			});
+
+	if !task.bind().is_async() || routine.bind().is_async() {
+		log_err("Expected `is_async` to be true only for async tasks");
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+			task.call("kill", &[]);
+
+			yield real_seconds(0.5);
+
+			if completed.load(Ordering::Relaxed) {
+				log_err("Expected killing an async task to cancel its background future");
+			}
+
+			log("Test 43 finished");
+
+			test_44(node_ref);
+		});
+}
+
+
+fn test_44(node: Gd<Node>) {
+	log("Starting test 44");
+
+	let flag = Rc::new(Cell::new(false));
+	let flag_clone = flag.clone();
+
+	let chained =
+		node.start_coroutine(
+			#[coroutine] move || {
+				yield frames(1).then_wait(wait_until(move || flag_clone.get()));
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			let start = Engine::singleton().get_process_frames();
+			yield frames(2).then_wait(frames(3));
+
+			let passed = Engine::singleton().get_process_frames() - start;
+			if passed != 5 {
+				log_err(format!("Expected `frames(2).then_wait(frames(3))` to wait 5 frames, waited: {passed}"));
+			}
+
+			if chained.bind().is_finished() {
+				log_err("Expected `then_wait` to keep waiting on its last yield");
+			}
+
+			flag.set(true);
+			yield chained.wait_until_finished();
+
+			log("Test 44 finished");
+
+			test_45(node_ref);
+		});
+}
+
+
+fn test_45(node: Gd<Node>) {
+	log("Starting test 45");
+
+	let root = node.get_tree().unwrap().get_root().unwrap();
+
+	let on_root =
+		start_coroutine_on(root.clone(),
+			#[coroutine] || {
+				yield frames(1);
+			});
+
+	let on_root_task = start_async_task_on(root.clone(), async { 45 });
+
+	for coroutine in [&on_root, &on_root_task] {
+		if coroutine.get_parent() != Some(root.clone().upcast()) {
+			log_err("Expected `start_coroutine_on`/`start_async_task_on` to add the coroutine as a child of the given parent");
+		}
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield on_root.wait_until_finished();
+			yield on_root_task.wait_until_finished();
+			log("Test 45 finished");
+
+			test_46(node_ref);
+		});
+}
+
+
+fn test_46(node: Gd<Node>) {
+	log("Starting test 46");
+
+	let calls = Rc::new(Cell::new(0));
+
+	let producer = {
+		let calls = calls.clone();
+
+		Callable::from_local_fn("test_46_producer", move |_| {
+			calls.set(calls.get() + 1);
+
+			if calls.get() < 3 {
+				Ok(Variant::nil())
+			} else {
+				Ok("ready".to_variant())
+			}
+		})
+	};
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			let (wait, value) = wait_for_some(producer);
+			yield wait;
+
+			if *value.borrow() != Some("ready".to_variant()) || calls.get() != 3 {
+				log_err(format!("Expected `wait_for_some` to resume with \"ready\" after 3 calls, got: {:?} after {} calls",
+					value.borrow(), calls.get()));
+			}
+
+			log("Test 46 finished");
+
+			test_47(node_ref);
+		});
+}
+
+
+fn test_47(node: Gd<Node>) {
+	log("Starting test 47");
+
+	let waiter =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(10);
+				yield seconds(10.0);
+				yield wait_until(|| false);
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			let mut waiter = waiter;
+			let mut kinds = Vec::new();
+
+			for _ in 0..3 {
+				yield frames(1);
+
+				let description = waiter.call("describe_wait", &[]).to::<Dictionary>();
+				kinds.push(description.get("kind").map(|kind| kind.to::<String>()).unwrap_or_default());
+
+				if description.len() != 3 {
+					log_err(format!("Expected `describe_wait` to return exactly 3 entries, got: {description:?}"));
+				}
+
+				waiter.bind_mut().skip_wait();
+			}
+
+			if kinds != ["frames", "seconds", "dyn"] {
+				log_err(format!("Expected `describe_wait` kinds [frames, seconds, dyn], got: {kinds:?}"));
+			}
+
+			waiter.bind_mut().kill();
+			log("Test 47 finished");
+
+			test_48(node_ref);
+		});
+}
+
+
+fn test_48(node: Gd<Node>) {
+	log("Starting test 48");
+
+	let mut coroutine =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(2);
+			});
+
+	let checked = Rc::new(Cell::new(false));
+
+	let handler = {
+		let coroutine = coroutine.clone();
+		let checked = checked.clone();
+
+		Callable::from_local_fn("test_48_finished", move |_| {
+			checked.set(true);
+
+			if !coroutine.is_inside_tree() || coroutine.is_queued_for_deletion() {
+				log_err("Expected the coroutine to still be in the tree, and not queued for deletion, while emitting `finished`");
+			}
+
+			let bind = coroutine.bind();
+			if !bind.is_finished() || bind.get_elapsed_frames() == 0 {
+				log_err("Expected the coroutine to be readable, and already finished, while emitting `finished`");
+			}
+
+			Ok(Variant::nil())
+		})
+	};
+
+	coroutine.connect(SIGNAL_FINISHED, &handler);
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield coroutine.wait_until_finished();
+
+			if !checked.get() {
+				log_err("Expected `finished` to be emitted");
+			}
+
+			log("Test 48 finished");
+
+			test_49(node_ref);
+		});
+}
+
+
+fn test_49(node: Gd<Node>) {
+	log("Starting test 49");
+
+	let ready = Rc::new(Cell::new(false));
+
+	let mut drivable =
+		node.start_coroutine(
+			#[coroutine] move || {
+				ready.set(true);
+				yield wait_until(move || ready.get());
+				yield frames(1);
+				yield wait_while(|| false);
+				49
+			});
+
+	let result = drivable.bind_mut().drive_to_completion(16);
+	if result != Some(49.to_variant()) {
+		log_err(format!("Expected `drive_to_completion` to complete a coroutine with satisfiable waits, got: {result:?}"));
+	}
+
+	let mut blocked =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield seconds(1.0);
+			});
+
+	let result = blocked.bind_mut().drive_to_completion(16);
+	if result.is_some() || blocked.bind().is_finished() {
+		log_err("Expected `drive_to_completion` to bail on a seconds wait, without finishing the coroutine");
+	}
+
+	blocked.bind_mut().kill();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] || {
+			yield frames(1);
+			log("Test 49 finished");
+
+			test_50(node_ref);
+		});
+}
+
+
+fn test_50(node: Gd<Node>) {
+	log("Starting test 50");
+
+	let attempts = Rc::new(Cell::new(0));
+
+	let make = {
+		let attempts = attempts.clone();
+
+		move || {
+			let attempts = attempts.clone();
+
+			#[coroutine] move || {
+				attempts.set(attempts.get() + 1);
+				yield frames(1);
+
+				if attempts.get() < 3 {
+					panic!("Test 50: intentional panic on attempt {}", attempts.get());
+				}
+
+				attempts.get()
+			}
+		}
+	};
+
+	let service =
+		node.coroutine(make())
+			.restart_on_panic(2, make)
+			.spawn();
+
+	let fragile =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(1);
+
+				let should_panic = true;
+				if should_panic {
+					panic!("Test 50: intentional panic without restarts left");
+				}
+			})
+			.restart_on_panic(0, || #[coroutine] || { yield frames(1); })
+			.spawn();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield service.wait_until_finished();
+			yield fragile.wait_until_finished();
+
+			if attempts.get() != 3 {
+				log_err(format!("Expected the service to finish on its 3rd attempt, attempts: {}", attempts.get()));
+			}
+
+			log("Test 50 finished");
+
+			test_51(node_ref);
+		});
+}
+
+
+fn test_51(node: Gd<Node>) {
+	log("Starting test 51");
+
+	let mut animation =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield seconds(3.0);
+				yield seconds(1.0);
+				51
+			});
+
+	let result = animation.bind_mut().force_run_to_completion_with_step(0.1, 100);
+	let elapsed = animation.bind().get_elapsed_seconds();
+
+	if result != 51.to_variant() || !(3.9..=4.3).contains(&elapsed) {
+		log_err(format!("Expected to complete with 51 after ~4 simulated seconds, got: {result:?} after {elapsed} seconds"));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] || {
+			yield frames(1);
+			log("Test 51 finished");
+
+			test_52(node_ref);
+		});
+}
+
+
+fn test_52(node: Gd<Node>) {
+	log("Starting test 52");
+
+	let received = Rc::new(Cell::new(0));
+	let cancelled = Rc::new(Cell::new(false));
+
+	let mut killable = {
+		let received = received.clone();
+		let cancelled = cancelled.clone();
+
+		node.coroutine(
+				#[coroutine] || {
+					yield seconds(10.0);
+					100
+				})
+			.emit_on_kill((-1).to_variant())
+			.on_cancel(move || cancelled.set(true))
+			.on_finish(move |result| received.set(result))
+			.spawn()
+	};
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+			killable.bind_mut().kill();
+
+			if received.get() != -1 || !cancelled.get() {
+				log_err(format!("Expected kill to invoke on_cancel and finish with -1, got: {} (cancelled: {})", 
+					received.get(), cancelled.get()));
+			}
+
+			log("Test 52 finished");
+
+			test_53(node_ref);
+		});
+}
+
+
+fn test_53(node: Gd<Node>) {
+	log("Starting test 53");
+
+	let control = Control::new_alloc();
+	node.clone().add_child(&control);
+
+	let waiter = {
+		let control = control.clone();
+		let node = node.clone();
+
+		node.clone().start_coroutine(
+			#[coroutine] move || {
+				yield wait_for_resized(control.clone());
+				yield wait_for_visibility_changed(control);
+				yield wait_for_tree_changed(node);
+			})
+	};
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			let mut control = control;
+
+			yield frames(1);
+			control.set_size(Vector2::new(64.0, 32.0));
+			yield frames(1);
+			control.hide();
+			yield frames(1);
+			control.queue_free();
+			yield frames(2);
+
+			if !waiter.bind().is_finished() {
+				log_err("Expected `wait_for_resized`, `wait_for_visibility_changed` and `wait_for_tree_changed` to resume");
+			}
+
+			log("Test 53 finished");
+
+			test_54(node_ref);
+		});
+}
+
+
+fn test_54(node: Gd<Node>) {
+	log("Starting test 54");
+
+	let typed_result = Rc::new(RefCell::new(String::new()));
+	let signal_result = Rc::new(RefCell::new(String::new()));
+
+	let mut task = {
+		let typed_result = typed_result.clone();
+
+		node.async_task(async {
+				smol::Timer::after(Duration::from_millis(10)).await;
+				"downloaded".to_string()
+			})
+			.on_finish(move |result: String| *typed_result.borrow_mut() = result)
+			.spawn()
+	};
+
+	{
+		let signal_result = signal_result.clone();
+
+		task.connect(SIGNAL_FINISHED, &Callable::from_local_fn("test_54_finished", move |args| {
+			*signal_result.borrow_mut() = args[0].to::<String>();
+			Ok(Variant::nil())
+		}));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield task.wait_until_finished();
+
+			if *typed_result.borrow() != "downloaded" || *signal_result.borrow() != "downloaded" {
+				log_err(format!("Expected both `on_finish` and `finished` to receive the async task's result, got: {:?} and {:?}",
+					typed_result.borrow(), signal_result.borrow()));
+			}
+
+			log("Test 54 finished");
+
+			test_55(node_ref);
+		});
+}
+
+
+fn test_55(node: Gd<Node>) {
+	log("Starting test 55");
+
+	let mut new_parent = Node::new_alloc();
+	new_parent.set_process_mode(ProcessMode::ALWAYS);
+	node.clone().add_child(&new_parent);
+
+	let moving = {
+		let new_parent = new_parent.clone();
+
+		node.start_coroutine(
+			#[coroutine] move || {
+				yield reparent_to(new_parent.clone());
+
+				let parent = SpireCoroutine::current().and_then(|coroutine| coroutine.get_parent());
+				if parent != Some(new_parent) {
+					log_err("Expected `reparent_to` to move the coroutine before resuming");
+				}
+			})
+	};
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield moving.wait_until_finished();
+			new_parent.queue_free();
+			log("Test 55 finished");
+
+			test_56(node_ref);
+		});
+}
+
+
+fn test_56(node: Gd<Node>) {
+	log("Starting test 56");
+
+	let mut driven =
+		node.coroutine(
+			#[coroutine] || {
+				yield seconds(0.25);
+				56
+			})
+			.auto_start(false)
+			.spawn();
+
+	driven.call("tool_step", &[1.0.to_variant()]);
+	if driven.bind().get_elapsed_seconds() != 0.0 {
+		log_err("Expected `tool_step` to respect pause");
+	}
+
+	driven.bind_mut().resume();
+
+	let mut result = Variant::nil();
+	for _ in 0..10 {
+		result = driven.call("tool_step", &[0.1.to_variant()]);
+
+		if !result.is_nil() {
+			break;
+		}
+	}
+
+	if result != 56.to_variant() {
+		log_err(format!("Expected `tool_step` to drive the coroutine to completion, got: {result:?}"));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] || {
+			yield frames(1);
+			log("Test 56 finished");
+
+			test_57(node_ref);
+		});
+}
+
+
+fn test_57(node: Gd<Node>) {
+	log("Starting test 57");
+
+	let mut deferred =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(1);
+			})
+			.spawn_paused();
+
+	if !deferred.is_paused() {
+		log_err("Expected `spawn_paused` to spawn the coroutine paused");
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(3);
+
+			if deferred.is_finished() {
+				log_err("Expected a coroutine spawned with `spawn_paused` to not run until started");
+			}
+
+			deferred.call("start", &[]);
+			yield deferred.wait_until_finished();
+
+			log("Test 57 finished");
+
+			test_58(node_ref);
+		});
+}
+
+
+fn test_58(node: Gd<Node>) {
+	log("Starting test 58");
+
+	let mut emitter = Node::new_alloc();
+	emitter.add_user_signal("ping");
+	node.clone().add_child(&emitter);
+
+	let signal = Signal::from_object_signal(&emitter, "ping");
+
+	let waiter =
+		node.start_coroutine(
+			#[coroutine] move || {
+				yield signal.into();
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(2);
+
+			if waiter.is_finished() {
+				log_err("Expected yielding a `Signal` to wait until it's emitted");
+			}
+
+			emitter.emit_signal("ping", &[]);
+			yield waiter.wait_until_finished();
+
+			emitter.queue_free();
+			log("Test 58 finished");
+
+			test_59(node_ref);
+		});
+}
+
+
+fn test_59(node: Gd<Node>) {
+	log("Starting test 59");
+
+	let mut settings = CoroutineSettings::new_gd();
+	{
+		let mut bind = settings.bind_mut();
+		bind.poll_mode = PollMode::Physics;
+		bind.process_mode = ProcessMode::ALWAYS.ord();
+		bind.start_delay = 0.2;
+		bind.time_scale = 2.0;
+	}
+
+	let started = Rc::new(Cell::new(false));
+	let started_ref = started.clone();
+
+	let configured =
+		node.coroutine(
+			#[coroutine] move || {
+				started_ref.set(true);
+				yield frames(1);
+			})
+			.from_settings(&settings)
+			.spawn();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			{
+				let bind = configured.bind();
+
+				if !bind.get_poll_mode_is_physics() || bind.get_time_scale() != 2.0 {
+					log_err("Expected `from_settings` to apply the resource's poll mode and time scale");
+				}
+			}
+
+			if configured.get_process_mode() != ProcessMode::ALWAYS {
+				log_err("Expected `from_settings` to apply the resource's process mode");
+			}
+
+			yield frames(2);
+
+			if started.get() {
+				log_err("Expected `from_settings` to delay the coroutine's first step");
+			}
+
+			yield configured.wait_until_finished();
+
+			if !started.get() {
+				log_err("Expected the delayed coroutine to run after its start delay");
+			}
+
+			log("Test 59 finished");
+
+			test_60(node_ref);
+		});
+}
+
+
+fn test_60(node: Gd<Node>) {
+	log("Starting test 60");
+
+	let long_press =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield wait_for_long_press("ui_accept", 0.1);
+			});
+
+	let double_press =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield wait_for_double_press("ui_cancel", 1.0);
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			let mut input = godot::classes::Input::singleton();
+
+			yield frames(1);
+			input.action_press("ui_accept");
+			yield frames(1);
+
+			if long_press.is_finished() {
+				log_err("Expected `wait_for_long_press` to wait until the action was held for long enough");
+			}
+
+			yield long_press.wait_until_finished();
+			input.action_release("ui_accept");
+
+			input.action_press("ui_cancel");
+			yield frames(2);
+			input.action_release("ui_cancel");
+			yield frames(2);
+
+			if double_press.is_finished() {
+				log_err("Expected `wait_for_double_press` to keep waiting after a single press");
+			}
+
+			input.action_press("ui_cancel");
+			yield frames(2);
+			input.action_release("ui_cancel");
+
+			if !double_press.is_finished() {
+				log_err("Expected `wait_for_double_press` to resume after a second press within the window");
+			}
+
+			log("Test 60 finished");
+
+			test_61(node_ref);
+		});
+}
+
+
+fn test_61(node: Gd<Node>) {
+	log("Starting test 61");
+
+	let exits = Rc::new(Cell::new(0));
+
+	let counter = |exits: &Rc<Cell<i32>>| {
+		let exits = exits.clone();
+		move || exits.set(exits.get() + 1)
+	};
+
+	let finishing =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(1);
+			})
+			.on_exit(counter(&exits))
+			.spawn();
+
+	let mut killed =
+		node.coroutine(
+			#[coroutine] || {
+				yield seconds(60.0);
+			})
+			.on_exit(counter(&exits))
+			.spawn();
+
+	let mut owner = Node::new_alloc();
+	node.clone().add_child(&owner);
+
+	owner.coroutine(
+		#[coroutine] || {
+			yield seconds(60.0);
+		})
+		.on_exit(counter(&exits))
+		.spawn();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield finishing.wait_until_finished();
+
+			if exits.get() != 1 {
+				log_err(format!("Expected `on_exit` to run when the coroutine finishes, exits: {}", exits.get()));
+			}
+
+			killed.bind_mut().kill();
+
+			if exits.get() != 2 {
+				log_err(format!("Expected `on_exit` to run when the coroutine is killed, exits: {}", exits.get()));
+			}
+
+			owner.queue_free();
+			yield frames(2);
+
+			if exits.get() != 3 {
+				log_err(format!("Expected `on_exit` to run when the coroutine is freed with its owner, exits: {}", exits.get()));
+			}
+
+			log("Test 61 finished");
+
+			test_62(node_ref);
+		});
+}
+
+
+fn test_62(node: Gd<Node>) {
+	log("Starting test 62");
+
+	let mut producer =
+		node.coroutine(
+			#[coroutine] || {
+				for step in 1..=3 {
+					yield yield_value(step);
+				}
+			})
+			.auto_start(false)
+			.spawn();
+
+	let values = Rc::new(RefCell::new(Vec::new()));
+	let values_ref = values.clone();
+
+	producer.connect(SIGNAL_VALUE, &Callable::from_local_fn("record_value",
+		move |args| {
+			values_ref.borrow_mut().push(args[0].to::<i32>());
+			Ok(Variant::nil())
+		}));
+
+	producer.bind_mut().start();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield producer.wait_until_finished();
+
+			if *values.borrow() != [1, 2, 3] {
+				log_err(format!("Expected `yield_value` to emit every yielded value in order, got: {:?}", values.borrow()));
+			}
+
+			log("Test 62 finished");
+
+			test_63(node_ref);
+		});
+}
+
+
+fn test_63(node: Gd<Node>) {
+	log("Starting test 63");
+
+	let waited = Rc::new(Cell::new(0.0));
+	let waited_ref = waited.clone();
+
+	let delta_aware =
+		node.start_coroutine(
+			#[coroutine] move || {
+				yield wait_until(move |delta: f64| {
+					waited_ref.set(waited_ref.get() + delta);
+					waited_ref.get() >= 0.1
+				});
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			if delta_aware.is_finished() {
+				log_err("Expected a delta-aware `wait_until` to keep waiting until enough delta time accumulated");
+			}
+
+			yield delta_aware.wait_until_finished();
+
+			if waited.get() < 0.1 {
+				log_err(format!("Expected a delta-aware `wait_until` to receive the poll's delta time, got: {}", waited.get()));
+			}
+
+			log("Test 63 finished");
+
+			test_64(node_ref);
+		});
+}
+
+
+fn test_64(node: Gd<Node>) {
+	log("Starting test 64");
+
+	let mut other_owner = Node::new_alloc();
+	node.clone().add_child(&other_owner);
+
+	let spawn_tagged = |owner: &Gd<Node>, group: &str| {
+		owner.coroutine(
+			#[coroutine] || {
+				yield seconds(60.0);
+			})
+			.group(group)
+			.spawn()
+	};
+
+	let ai_first = spawn_tagged(&node, "test_64_ai");
+	let ai_second = spawn_tagged(&other_owner, "test_64_ai");
+	let ui = spawn_tagged(&node, "test_64_ui");
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			if ai_first.bind().get_group() != StringName::from("test_64_ai") {
+				log_err("Expected `get_group` to return the coroutine's tag");
+			}
+
+			pause_group("test_64_ai");
+
+			if !ai_first.is_paused() || !ai_second.is_paused() || ui.is_paused() {
+				log_err("Expected `pause_group` to pause only the coroutines with that tag, across owners");
+			}
+
+			kill_group("test_64_ai");
+
+			if !ai_first.is_finished() || !ai_second.is_finished() || ui.is_finished() {
+				log_err("Expected `kill_group` to kill only the coroutines with that tag, across owners");
+			}
+
+			if coroutines_in_group("test_64_ui").len() != 1 {
+				log_err("Expected `coroutines_in_group` to find the remaining tagged coroutine");
+			}
+
+			kill_group("test_64_ui");
+			other_owner.queue_free();
+			yield frames(1);
+
+			log("Test 64 finished");
+
+			test_65(node_ref);
+		});
+}
+
+
+fn test_65(node: Gd<Node>) {
+	log("Starting test 65");
+
+	let clock = VirtualClock::new(node.clone(), 0.1);
+
+	let short =
+		clock.start(
+			#[coroutine] || {
+				yield seconds(1.0);
+			});
+
+	let long =
+		clock.start(
+			#[coroutine] || {
+				yield seconds(2.5);
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(2);
+
+			if short.bind().get_elapsed_seconds() != 0.0 {
+				log_err("Expected coroutines driven by a `VirtualClock` not to be polled by the engine");
+			}
+
+			clock.set_virtual_time(2.0);
+
+			if !short.is_finished() || long.is_finished() {
+				log_err("Expected `set_virtual_time` to advance members by the time that passed");
+			}
+
+			if clock.set_virtual_time(1.0) != 2.0 || clock.time() != 2.0 {
+				log_err("Expected `set_virtual_time` to clamp when moving backward");
+			}
+
+			clock.set_virtual_time(3.0);
+
+			if !long.is_finished() || !clock.coroutines().is_empty() {
+				log_err("Expected `set_virtual_time` to keep advancing members after clamping");
+			}
+
+			log("Test 65 finished");
+
+			test_66(node_ref);
+		});
+}
+
+
+fn test_66(node: Gd<Node>) {
+	log("Starting test 66");
+
+	let coroutine =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(1);
+				7
+			});
+
+	let received = Rc::new(Cell::new(None));
+	let received_ref = received.clone();
+
+	coroutine.bind().connect_finished(move |result: i32| received_ref.set(Some(result)));
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield coroutine.wait_until_finished();
+
+			if received.get() != Some(7) {
+				log_err(format!("Expected `connect_finished` to receive the converted result, got: {:?}", received.get()));
+			}
+
+			log("Test 66 finished");
+
+			test_67(node_ref);
+		});
+}
+
+
+fn test_67(node: Gd<Node>) {
+	log("Starting test 67");
+
+	let mut kept =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(1);
+				"Loaded"
+			})
+			.keep_alive(true)
+			.spawn();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			if kept.bind().is_result_ready() {
+				log_err("Expected `is_result_ready` to be false before the coroutine finishes");
+			}
+
+			yield kept.wait_until_finished();
+			yield frames(2);
+
+			if !kept.is_instance_valid() || kept.get_parent().is_none() {
+				log_err("Expected a kept alive coroutine to stay in the tree after finishing");
+			} else {
+				let result = kept.bind().try_get_result();
+
+				if !kept.bind().is_result_ready() || result.try_to::<GString>().ok() != Some(GString::from("Loaded")) {
+					log_err(format!("Expected `try_get_result` to return the stored result, got: {result}"));
+				}
+
+				kept.queue_free();
+			}
+
+			log("Test 67 finished");
+
+			test_68(node_ref);
+		});
+}
+
+
+fn test_68(node: Gd<Node>) {
+	log("Starting test 68");
+
+	let batch =
+		node.spawn_many(0..5, |index: i64| {
+			#[coroutine] move || {
+				yield frames(index);
+				index * 2
+			}
+		});
+
+	let results = Rc::new(RefCell::new(None));
+	let results_ref = results.clone();
+	batch.host().bind().connect_finished(move |array: VariantArray| *results_ref.borrow_mut() = Some(array));
+
+	if batch.len() != 5 {
+		log_err(format!("Expected a new batch to have 5 members, got: {}", batch.len()));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(2);
+
+			if batch.len() != 2 {
+				log_err(format!("Expected the batch to have 2 members left after 2 frames, got: {}", batch.len()));
+			}
+
+			yield batch.wait_all();
+
+			let expected: Vec<i64> = (0..5).map(|index| index * 2).collect();
+			let received: Option<Vec<i64>> = results.borrow().as_ref().map(|array| array.iter_shared().map(|var| var.to::<i64>()).collect());
+
+			if received.as_ref() != Some(&expected) {
+				log_err(format!("Expected the batch to finish with every member's result in order, got: {received:?}"));
+			}
+
+			yield benchmark_spawn_many(1000);
+
+			log("Test 68 finished");
+
+			test_69(node_ref);
+		});
+}
+
+
+const TEST_69_PAUSE: Yield = frames(1);
+static TEST_69_STEPS: [WaitStep; 3] = frame_steps([1, 2, 3]);
+
+fn test_69(node: Gd<Node>) {
+	log("Starting test 69");
+
+	let sequence =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield TEST_69_PAUSE;
+				yield wait_sequence(&TEST_69_STEPS);
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(6);
+
+			if sequence.is_finished() {
+				log_err("Expected `wait_sequence` to wait on every step");
+			}
+
+			yield frames(1);
+
+			if !sequence.is_finished() {
+				log_err("Expected `wait_sequence` to resume once every step was waited on");
+			}
+
+			log("Test 69 finished");
+
+			test_70(node_ref);
+		});
+}
+
+
+fn test_70(node: Gd<Node>) {
+	log("Starting test 70");
+
+	let spawn_kept = |owner: &Gd<Node>, panics: bool| {
+		owner.coroutine(
+			#[coroutine] move || {
+				yield frames(1);
+
+				if panics {
+					panic!("Test 70: intentional panic");
+				}
+			})
+			.keep_alive(true)
+			.spawn()
+	};
+
+	let completed = spawn_kept(&node, false);
+	let panicked = spawn_kept(&node, true);
+	let mut killed = spawn_kept(&node, false);
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			if completed.bind().get_finish_reason() != FinishReason::Running {
+				log_err("Expected a running coroutine's finish reason to be `Running`");
+			}
+
+			killed.bind_mut().kill();
+			yield frames(2);
+
+			let reasons = [
+				(completed.bind().get_finish_reason(), FinishReason::Completed),
+				(panicked.bind().get_finish_reason(), FinishReason::Panicked),
+				(killed.bind().get_finish_reason(), FinishReason::Killed),
+			];
+
+			for (reason, expected) in reasons {
+				if reason != expected {
+					log_err(format!("Expected finish reason `{expected:?}`, got: `{reason:?}`"));
+				}
+			}
+
+			for mut coroutine in [completed, panicked, killed] {
+				coroutine.queue_free();
+			}
+
+			log("Test 70 finished");
+
+			test_71(node_ref);
+		});
+}
+
+
+fn test_71(node: Gd<Node>) {
+	log("Starting test 71");
+
+	// Expected to log an error, since the url is invalid.
+	let request = node.http_get("not a url");
+
+	let result = Rc::new(RefCell::new(None));
+	let result_ref = result.clone();
+	request.bind().connect_finished(move |var: Variant| *result_ref.borrow_mut() = Some(var));
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield request.wait_until_finished();
+
+			match &*result.borrow() {
+				Some(var) if var.is_nil() => {}
+				other => log_err(format!("Expected `http_get` to finish with nil on an invalid url, got: {other:?}")),
+			}
+
+			log("Test 71 finished");
+
+			test_72(node_ref);
+		});
+}
+
+
+fn test_72(node: Gd<Node>) {
+	log("Starting test 72");
+
+	let counter = Rc::new(Cell::new(0));
+
+	let counter_ref = counter.clone();
+	let simple = spire!(node, {
+		yield frames(1);
+		counter_ref.set(counter_ref.get() + 1);
+		42
+	});
+
+	let counter_ref = counter.clone();
+	let mut configured = spire!(node => auto_start: false, process_mode: ALWAYS, poll_mode: PollMode::Physics; {
+		yield frames(1);
+		counter_ref.set(counter_ref.get() + 10);
+	});
+
+	if !configured.is_paused() {
+		log_err("Expected `auto_start: false` to spawn a paused coroutine.");
+	}
+
+	if configured.get_process_mode() != ProcessMode::ALWAYS {
+		log_err(format!("Expected `process_mode: ALWAYS`, got: {:?}", configured.get_process_mode()));
+	}
+
+	if configured.bind().get_poll_mode() != PollMode::Physics {
+		log_err("Expected `poll_mode: PollMode::Physics`.");
+	}
+
+	configured.bind_mut().resume();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield simple.wait_until_finished();
+			yield configured.wait_until_finished();
+
+			if counter.get() != 11 {
+				log_err(format!("Expected both `spire!` coroutines to run, counter: {}", counter.get()));
+			}
+
+			log("Test 72 finished");
+
+			test_73(node_ref);
+		});
+}
+
+
+fn test_73(node: Gd<Node>) {
+	log("Starting test 73");
+
+	let trace = Rc::new(RefCell::new(Vec::new()));
+
+	let recorded =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(2);
+				yield frames(0);
+				yield seconds(0.2);
+				yield wait_until(|| true);
+			})
+			.record_yields(trace.clone())
+			.spawn();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield recorded.wait_until_finished();
+
+			let trace = trace.borrow();
+			let kinds = trace.iter().map(|record| record.kind).collect::<Vec<_>>();
+
+			if kinds != [YieldKind::Frames(2), YieldKind::Frames(0), YieldKind::Seconds(0.2), YieldKind::Custom] {
+				log_err(format!("Unexpected recorded yields: {trace:?}"));
+			} else {
+				if trace[0].elapsed_frames != 1 || trace[1].elapsed_frames != 3 || trace[2].elapsed_frames != 3 {
+					log_err(format!("Unexpected recorded frames: {trace:?}"));
+				}
+
+				if trace[3].elapsed_seconds < trace[2].elapsed_seconds + 0.2 {
+					log_err(format!("Expected the custom yield to be recorded after the seconds wait: {trace:?}"));
+				}
+			}
+
+			log("Test 73 finished");
+
+			test_74(node_ref);
+		});
+}
+
+
+fn test_74(mut node: Gd<Node>) {
+	log("Starting test 74");
+
+	let mut entity = Node::new_alloc();
+	node.add_child(&entity);
+	entity.set_process(true);
+
+	let polls = Rc::new(Cell::new(0));
+	let polls_ref = polls.clone();
+
+	let mirroring =
+		node.coroutine(
+			#[coroutine] move || {
+				for _ in 0..1000 {
+					polls_ref.set(polls_ref.get() + 1);
+					yield frames(0);
+					yield frames(1);
+				}
+			})
+			.mirror_owner_processing(entity.clone())
+			.spawn();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(3);
+
+			if polls.get() == 0 {
+				log_err("Expected the coroutine to run while the mirrored node is processing.");
+			}
+
+			entity.set_process(false);
+			yield frames(1);
+			let polls_when_disabled = polls.get();
+			let elapsed_when_disabled = mirroring.bind().get_elapsed_seconds();
+
+			yield frames(5);
+
+			if polls.get() != polls_when_disabled || mirroring.bind().get_elapsed_seconds() != elapsed_when_disabled {
+				log_err(format!("Expected the coroutine to be suspended while the mirrored node isn't processing, \
+				                 polls: {polls_when_disabled} -> {}", polls.get()));
+			}
+
+			entity.set_process(true);
+			yield frames(5);
+
+			if polls.get() <= polls_when_disabled {
+				log_err("Expected the coroutine to resume once the mirrored node processes again.");
+			}
+
+			mirroring.clone().bind_mut().kill();
+			entity.queue_free();
+
+			log("Test 74 finished");
+
+			test_75(node_ref);
+		});
+}
+
+
+fn test_75(node: Gd<Node>) {
+	log("Starting test 75");
+
+	let mut emitter = Node::new_alloc();
+	emitter.add_user_signal("pinged");
+	node.clone().add_child(&emitter);
+
+	let awaiter = SignalAwaiter::new(emitter.clone(), "pinged");
+	let waiting_on_signal =
+		node.start_coroutine(
+			#[coroutine] move || {
+				yield awaiter.yield_wait();
+			});
+
+	let waiting_on_frames =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(10);
+			});
+
+	let first = node.start_coroutine(#[coroutine] || { yield frames(10); });
+	let second = node.start_coroutine(#[coroutine] || { yield frames(10); });
+	let waiting_on_join =
+		node.start_coroutine(
+			#[coroutine] move || {
+				let (wait, _) = join_results([first, second]);
+				yield wait;
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			let description = waiting_on_signal.bind().get_awaiting_description();
+			if !description.contains("signal 'pinged'") {
+				log_err(format!("Unexpected description of a signal wait: {description}"));
+			}
+
+			let description = waiting_on_frames.bind().get_awaiting_description();
+			if !description.contains("frames") {
+				log_err(format!("Unexpected description of a frames wait: {description}"));
+			}
+
+			let description = waiting_on_join.bind().get_awaiting_description();
+			if description != "waiting on 2 of 2 coroutines" {
+				log_err(format!("Unexpected description of a join: {description}"));
+			}
+
+			emitter.emit_signal("pinged", &[]);
+			yield frames(1);
+
+			if !waiting_on_signal.is_finished() {
+				log_err("Expected the signal wait to resume.");
+			}
+
+			emitter.queue_free();
+
+			log("Test 75 finished");
+
+			test_76(node_ref);
+		});
+}
+
+
+fn test_76(node: Gd<Node>) {
+	log("Starting test 76");
+
+	// Driven manually, without any node.
+	let mut driver =
+		CoroutineDriver::new(
+			#[coroutine] || {
+				yield frames(2);
+				yield seconds(1.0);
+				yield wait_until(|| true);
+				"driven"
+			});
+
+	let mut ticks = 0;
+	let mut result = None;
+
+	while result.is_none() && ticks < 100 {
+		result = driver.tick(0.25);
+		ticks += 1;
+	}
+
+	if result != Some("driven".to_variant()) || ticks != 6 || !driver.is_finished() {
+		log_err(format!("Unexpected driver result: {result:?}, after {ticks} ticks"));
+	}
+
+	if driver.tick(0.25).is_some() || driver.elapsed_frames() != 6 {
+		log_err("Expected a finished driver to ignore further ticks.");
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+			log("Test 76 finished");
+
+			test_77(node_ref);
+		});
+}
+
+
+fn test_77(node: Gd<Node>) {
+	log("Starting test 77");
+
+	let long_wait =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(300);
+			});
+
+	let endless_wait =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(i64::MAX);
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(100);
+
+			let remaining = long_wait.bind().describe_wait().get("remaining").and_then(|var| var.try_to::<i64>().ok());
+			if remaining != Some(199) {
+				log_err(format!("Expected 199 remaining frames, got: {remaining:?}"));
+			}
+
+			yield frames(199);
+
+			if long_wait.is_finished() {
+				log_err("Expected `frames(300)` to still be waiting.");
+			}
+
+			yield frames(1);
+
+			if !long_wait.is_finished() {
+				log_err("Expected `frames(300)` to have resumed.");
+			}
+
+			if endless_wait.is_finished() {
+				log_err("Expected `frames(i64::MAX)` to keep waiting.");
+			}
+
+			endless_wait.clone().bind_mut().kill();
+
+			log("Test 77 finished");
+
+			test_78(node_ref);
+		});
+}
+
+
+fn test_78(node: Gd<Node>) {
+	log("Starting test 78");
+
+	let results = Rc::new(RefCell::new(Vec::new()));
+	let results_ref = results.clone();
+	let once_fired = Rc::new(Cell::new(false));
+	let once_ref = once_fired.clone();
+
+	let coroutine =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(2);
+				7
+			})
+			.on_each_finish(move |result: i32| results_ref.borrow_mut().push(result))
+			.on_finish(move |_| once_ref.set(true))
+			.spawn();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield coroutine.wait_until_finished();
+
+			if *results.borrow() != [7] || !once_fired.get() {
+				log_err(format!("Expected `on_each_finish` and `on_finish` to fire once, results: {:?}", results.borrow()));
+			}
+
+			log("Test 78 finished");
+
+			test_79(node_ref);
+		});
+}
+
+
+fn test_79(node: Gd<Node>) {
+	log("Starting test 79");
+
+	let finished = Rc::new(Cell::new(false));
+	let finished_ref = finished.clone();
+
+	start_coroutine_detached_forget(
+		#[coroutine] move || {
+			SpireCoroutine::current().unwrap().set_name("DetachedForget");
+			yield frames(2);
+			finished_ref.set(true);
+		});
+
+	let root = node.get_tree().unwrap().get_root().unwrap();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			if root.get_node_or_null("DetachedForget").is_none() {
+				log_err("Expected the detached coroutine to be a child of the root.");
+			}
+
+			yield frames(3);
+
+			if !finished.get() {
+				log_err("Expected the detached coroutine to finish.");
+			}
+
+			if root.get_node_or_null("DetachedForget").is_some() {
+				log_err("Expected the detached coroutine's node to be removed once it finished.");
+			}
+
+			log("Test 79 finished");
+
+			test_80(node_ref);
+		});
+}
+
+
+fn test_80(node: Gd<Node>) {
+	log("Starting test 80");
+
+	let mut emitter = Node::new_alloc();
+	emitter.add_user_signal("pinged");
+	node.clone().add_child(&emitter);
+
+	let resumed = Rc::new(Cell::new(false));
+	let resumed_ref = resumed.clone();
+	let signal_emitter = emitter.clone();
+
+	let waiting =
+		node.start_coroutine(
+			#[coroutine] move || {
+				let (wait, args) = wait_for_signal(signal_emitter, "pinged");
+				yield wait;
+
+				if args.borrow().as_ref().map(|args| args.iter().map(|arg| arg.to::<i32>()).collect::<Vec<_>>()) != Some(vec![80]) {
+					log_err(format!("Expected `wait_for_signal` to capture the signal's args, got: {:?}", args.borrow()));
+				}
+
+				resumed_ref.set(true);
+			});
+
+	let killed_emitter = emitter.clone();
+	let mut killed =
+		node.start_coroutine(
+			#[coroutine] move || {
+				yield wait_for_signal(killed_emitter.clone(), "pinged").0;
+				yield wait_for_signal(killed_emitter, "pinged").0;
+			});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			if emitter.get_signal_connection_list("pinged").len() != 2 {
+				log_err("Expected both coroutines to be connected to the signal.");
+			}
+
+			killed.bind_mut().kill();
+
+			if emitter.get_signal_connection_list("pinged").len() != 1 {
+				log_err("Expected the killed coroutine's connection to be removed.");
+			}
+
+			emitter.emit_signal("pinged", &[80.to_variant()]);
+			yield frames(1);
+
+			if !resumed.get() || !waiting.is_finished() {
+				log_err("Expected `wait_for_signal` to resume once the signal fired.");
+			}
+
+			if !emitter.get_signal_connection_list("pinged").is_empty() {
+				log_err("Expected no connections left after the signal fired.");
+			}
+
+			emitter.queue_free();
+
+			log("Test 80 finished");
+
+			test_81(node_ref);
+		});
+}
+
+
+fn test_81(node: Gd<Node>) {
+	log("Starting test 81");
+
+	let mut engine = Engine::singleton();
+	let previous_time_scale = engine.get_time_scale();
+
+	let ticks_until_finished = |f: fn() -> Yield| {
+		let mut driver =
+			CoroutineDriver::new(
+				#[coroutine] move || {
+					yield f();
+				});
+
+		(1..=20).find(|_| driver.tick(0.05).is_some())
+	};
+
+	// With a time scale of 0.5, each tick of 0.05 scaled seconds lasts 0.1 real seconds.
+	engine.set_time_scale(0.5);
+	let unscaled_ticks = ticks_until_finished(|| seconds_unscaled(0.27));
+	let scaled_ticks = ticks_until_finished(|| seconds(0.27));
+
+	engine.set_time_scale(0.0);
+	let frozen_ticks = ticks_until_finished(|| seconds_unscaled(0.27));
+
+	engine.set_time_scale(previous_time_scale);
+
+	if unscaled_ticks != Some(3) || scaled_ticks != Some(6) || frozen_ticks.is_some() {
+		log_err(format!("Unexpected ticks, unscaled: {unscaled_ticks:?}, scaled: {scaled_ticks:?}, frozen: {frozen_ticks:?}"));
+	}
+
+	let mut waiting = node.start_coroutine(#[coroutine] || { yield seconds_unscaled(5.0); });
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield seconds_unscaled(0.1);
+
+			let remaining = waiting.bind().remaining_seconds();
+			let kind = waiting.bind().describe_wait().get("kind");
+
+			if !remaining.is_some_and(|remaining| remaining > 4.0 && remaining < 5.0) || kind != Some("seconds_unscaled".to_variant()) {
+				log_err(format!("Expected the unscaled wait to be reported, remaining: {remaining:?}, kind: {kind:?}"));
+			}
+
+			waiting.bind_mut().kill();
+
+			log("Test 81 finished");
+
+			test_82(node_ref);
+		});
+}
+
+
+fn test_82(node: Gd<Node>) {
+	log("Starting test 82");
+
+	let pending = Node::new_alloc();
+	let waited_node = pending.clone();
+	let resumed = Rc::new(Cell::new(false));
+	let resumed_ref = resumed.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield wait_for_node_ready(waited_node.clone());
+			resumed_ref.set(waited_node.is_node_ready());
+		});
+
+	let doomed = Node::new_alloc();
+	let freed_resumed = Rc::new(Cell::new(false));
+	let freed_resumed_ref = freed_resumed.clone();
+	let doomed_ref = doomed.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield wait_for_node_ready(doomed_ref);
+			freed_resumed_ref.set(true);
+		});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(2);
+
+			if resumed.get() || freed_resumed.get() {
+				log_err("Expected `wait_for_node_ready` to keep waiting on nodes outside the tree.");
+			}
+
+			node_ref.clone().add_child(&pending);
+			doomed.free();
+			yield frames(1);
+
+			if !resumed.get() {
+				log_err("Expected `wait_for_node_ready` to resume once the node was ready.");
+			}
+
+			if !freed_resumed.get() {
+				log_err("Expected `wait_for_node_ready` to resume once the node was freed.");
+			}
+
+			pending.clone().queue_free();
+
+			log("Test 82 finished");
+
+			test_83(node_ref);
+		});
+}
+
+
+fn test_83(node: Gd<Node>) {
+	log("Starting test 83");
+
+	let short = node.start_coroutine(#[coroutine] || { yield frames(1); });
+	let long = node.start_coroutine(#[coroutine] || { yield frames(4); });
+	let mut killed = node.start_coroutine(#[coroutine] || { yield frames(100); });
+
+	let resumed = Rc::new(Cell::new(false));
+	let resumed_ref = resumed.clone();
+	let long_ref = long.clone();
+	let killed_ref = killed.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield wait_all([short, long_ref, killed_ref]);
+			resumed_ref.set(true);
+		});
+
+	let empty_resumed = Rc::new(Cell::new(false));
+	let empty_resumed_ref = empty_resumed.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield wait_all(Vec::new());
+			empty_resumed_ref.set(true);
+		});
+
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			if !empty_resumed.get() {
+				log_err("Expected `wait_all` with no coroutines to resume right away.");
+			}
+
+			killed.bind_mut().kill();
+			yield frames(2);
+
+			if resumed.get() {
+				log_err("Expected `wait_all` to keep waiting while a coroutine is running.");
+			}
+
+			yield frames(2);
+
+			if !resumed.get() || !long.is_finished() {
+				log_err("Expected `wait_all` to resume once every coroutine ended.");
+			}
+
+			log("Test 83 finished");
+
+			test_84(node_ref);
+		});
+}
+
+
+fn test_84(node: Gd<Node>) {
+	log("Starting test 84");
+
+	let mut work = node.start_coroutine(#[coroutine] || { yield frames(100); });
+	let timeout = node.start_coroutine(#[coroutine] || { yield frames(2); });
+
+	let first = Rc::new(Cell::new(None));
+	let first_ref = first.clone();
+	let work_ref = work.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			let (wait, first) = wait_any([work_ref, timeout]);
+			yield wait;
+			first_ref.set(first.get());
+		});
+
+	let empty_first = Rc::new(Cell::new(Some(0)));
+	let empty_first_ref = empty_first.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			let (wait, first) = wait_any(Vec::new());
+			yield wait;
+			empty_first_ref.set(first.get());
+		});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			if empty_first.get().is_some() {
+				log_err("Expected `wait_any` with no coroutines to resume right away without a winner.");
+			}
+
+			if first.get().is_some() {
+				log_err("Expected `wait_any` to keep waiting while every coroutine is running.");
+			}
+
+			yield frames(3);
+
+			if first.get() != Some(1) {
+				log_err(format!("Expected `wait_any` to report the timeout as the first to end, got: {:?}", first.get()));
+			}
+
+			if work.is_finished() {
+				log_err("Expected `wait_any` to leave the other coroutines running.");
+			}
+
+			work.bind_mut().kill();
+
+			log("Test 84 finished");
+
+			test_85(node_ref);
+		});
+}
+
+
+fn test_85(node: Gd<Node>) {
+	log("Starting test 85");
+
+	let ran = Rc::new(Cell::new(false));
+	let ran_ref = ran.clone();
+
+	node.coroutine(
+		#[coroutine] move || {
+			ran_ref.set(true);
+			yield frames(1);
+		})
+		.start_mode(CoroutineStartMode::Deferred)
+		.spawn();
+
+	if ran.get() {
+		log_err("Expected a deferred coroutine not to run during `spawn`.");
+	}
+
+	let ran_immediately = Rc::new(Cell::new(false));
+	let ran_immediately_ref = ran_immediately.clone();
+
+	let finished =
+		node.coroutine(
+			#[coroutine] move || {
+				if false {
+					yield frames(1);
+				}
+
+				ran_immediately_ref.set(true);
+			})
+			.start_mode(CoroutineStartMode::Immediate)
+			.spawn();
+
+	if !ran_immediately.get() {
+		log_err("Expected an immediate coroutine to run during `spawn`.");
+	}
+
+	if !finished.is_instance_valid() || !finished.is_finished() {
+		log_err("Expected a coroutine that finished during `spawn` to be returned as a valid, finished node.");
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			if !ran.get() {
+				log_err("Expected a deferred coroutine to run on its first poll.");
+			}
+
+			log("Test 85 finished");
+
+			test_86(node_ref);
+		});
+}
+
+
+fn test_86(node: Gd<Node>) {
+	log("Starting test 86");
+
+	let named =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(1);
+			})
+			.named("OpenDoor")
+			.spawn();
+
+	if named.get_name() != StringName::from("OpenDoor") {
+		log_err(format!("Expected the coroutine to be named `OpenDoor`, got: {}", named.get_name()));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			log("Test 86 finished");
+
+			test_87(node_ref);
+		});
+}
+
+
+fn test_87(node: Gd<Node>) {
+	log("Starting test 87");
+
+	let prioritized =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(1);
+			})
+			.process_priority(-10)
+			.physics_process_priority(-20)
+			.spawn();
+
+	if prioritized.get_process_priority() != -10 || prioritized.get_physics_process_priority() != -20 {
+		log_err(format!("Expected priorities (-10, -20), got: ({}, {})", 
+			prioritized.get_process_priority(), prioritized.get_physics_process_priority()));
+	}
+
+	let default = node.start_coroutine(#[coroutine] || { yield frames(1); });
+
+	if default.get_process_priority() != 256 || default.get_physics_process_priority() != 256 {
+		log_err(format!("Expected default priorities (256, 256), got: ({}, {})", 
+			default.get_process_priority(), default.get_physics_process_priority()));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			log("Test 87 finished");
+
+			test_88(node_ref);
+		});
+}
+
+
+fn test_88(node: Gd<Node>) {
+	log("Starting test 88");
+
+	let runs = Rc::new(Cell::new(0));
+	let runs_ref = runs.clone();
+	let make = move || {
+		let runs = runs_ref.clone();
+		#[coroutine] move || {
+			runs.set(runs.get() + 1);
+			yield frames(2);
+			runs.get()
+		}
+	};
+
+	let finishes = Rc::new(Cell::new(0));
+	let finishes_ref = finishes.clone();
+
+	let mut restartable =
+		node.coroutine(make())
+			.restartable(make)
+			.keep_alive(true)
+			.on_each_finish(move |_: i32| finishes_ref.set(finishes_ref.get() + 1))
+			.spawn();
+
+	let mut not_restartable = node.start_coroutine(#[coroutine] || { yield frames(2); });
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			restartable.bind_mut().restart();
+
+			if restartable.bind().get_elapsed_frames() != 0 {
+				log_err("Expected `restart` to reset the elapsed frames.");
+			}
+
+			yield frames(1);
+
+			if runs.get() != 2 {
+				log_err(format!("Expected the restarted closure to run from the beginning, runs: {}", runs.get()));
+			}
+
+			// Logs an error, but shouldn't affect the coroutine.
+			not_restartable.bind_mut().restart();
+
+			if !not_restartable.is_running() {
+				log_err("Expected `restart` to do nothing on coroutines that aren't restartable.");
+			}
+
+			yield frames(3);
+
+			if finishes.get() != 1 || restartable.bind().try_get_result() != 2.to_variant() {
+				log_err(format!("Expected the restarted coroutine to finish once with 2, finishes: {}, result: {}", 
+					finishes.get(), restartable.bind().try_get_result()));
+			}
+
+			restartable.bind_mut().restart();
+			yield frames(4);
+
+			if finishes.get() != 2 || restartable.bind().try_get_result() != 3.to_variant() {
+				log_err(format!("Expected the finished coroutine to run again after `restart`, finishes: {}, result: {}", 
+					finishes.get(), restartable.bind().try_get_result()));
+			}
+
+			restartable.queue_free();
+
+			log("Test 88 finished");
+
+			test_89(node_ref);
+		});
+}
+
+
+fn test_89(node: Gd<Node>) {
+	log("Starting test 89");
+
+	let mut timed =
+		node.coroutine(
+			#[coroutine] || {
+				yield frames(3);
+				yield seconds(1.0);
+				yield wait_until(|| false);
+			})
+			.spawn_paused();
+
+	let mut bind = timed.bind_mut();
+
+	if bind.remaining_frames().is_some() || bind.remaining_seconds().is_some() {
+		log_err("Expected no remaining wait before the first poll.");
+	}
+
+	bind.step(0.1);
+
+	if bind.remaining_frames() != Some(2) || bind.remaining_seconds().is_some() {
+		log_err(format!("Expected 2 remaining frames, got: {:?}", bind.remaining_frames()));
+	}
+
+	bind.step(0.1);
+	bind.step(0.1);
+	bind.step(0.25);
+
+	let remaining_seconds = bind.remaining_seconds().unwrap_or_default();
+	if (remaining_seconds - 0.75).abs() > 1e-9 || bind.remaining_frames().is_some() {
+		log_err(format!("Expected 0.75 remaining seconds, got: {:?}", bind.remaining_seconds()));
+	}
+
+	bind.step(1.0);
+
+	if bind.remaining_frames().is_some() || bind.remaining_seconds().is_some() {
+		log_err("Expected no remaining time while waiting on a custom yield.");
+	}
+
+	bind.kill();
+	drop(bind);
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			log("Test 89 finished");
+
+			test_90(node_ref);
+		});
+}
+
+
+fn test_90(node: Gd<Node>) {
+	log("Starting test 90");
+
+	let finishing =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield frames(2);
+				42
+			});
+
+	let mut killed = node.start_coroutine(#[coroutine] || { yield frames(100); });
+
+	let finished_future = CoroutineFuture::<i32>::new(&finishing);
+	let killed_future = CoroutineFuture::<i32>::from(killed.clone());
+
+	let finished_task = node.start_async_task(async move { finished_future.await.unwrap_or(-1) });
+	let killed_task = node.start_async_task(async move { killed_future.await.is_none() });
+
+	let finished_result = Rc::new(RefCell::new(None));
+	let finished_result_ref = finished_result.clone();
+	finished_task.bind().connect_finished(move |result: i32| *finished_result_ref.borrow_mut() = Some(result));
+
+	let killed_result = Rc::new(Cell::new(false));
+	let killed_result_ref = killed_result.clone();
+	killed_task.bind().connect_finished(move |is_none: bool| killed_result_ref.set(is_none));
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+			killed.bind_mut().kill();
+
+			yield wait_until(move || finished_task.is_finished() && killed_task.is_finished());
+
+			if *finished_result.borrow() != Some(42) {
+				log_err(format!("Expected the awaited coroutine to resolve with 42, got: {:?}", finished_result.borrow()));
+			}
+
+			if !killed_result.get() {
+				log_err("Expected the awaited killed coroutine to resolve with `None`.");
+			}
+
+			log("Test 90 finished");
+
+			test_91(node_ref);
+		});
+}
+
+
+fn test_91(node: Gd<Node>) {
+	log("Starting test 91");
+
+	let mut forced =
+		node.start_coroutine(
+			#[coroutine] || {
+				yield seconds(10.0);
+				7
+			});
+
+	let result = forced.bind_mut().force_run_to_completion();
+
+	if result != 7.to_variant() {
+		log_err(format!("Expected `force_run_to_completion` to return 7, got: {result}"));
+	}
+
+	if forced.bind().get_finish_reason() != FinishReason::Completed {
+		log_err(format!("Expected a force-completed coroutine to report `Completed`, got: {:?}", forced.bind().get_finish_reason()));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			log("Test 91 finished");
+
+			test_92(node_ref);
+		});
+}
+
+
+fn test_92(node: Gd<Node>) {
+	log("Starting test 92");
+
+	let pool = CoroutinePool::new(1);
+
+	let mut first =
+		node.coroutine(#[coroutine] || { yield frames(100); })
+			.pool(&pool)
+			.spawn();
+
+	let resumed = Rc::new(Cell::new(false));
+	let resumed_ref = resumed.clone();
+	let awaited = first.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			let (wait, _) = join_results([awaited]);
+			yield wait;
+			resumed_ref.set(true);
+		});
+
+	let owner = node.clone();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			// Killed coroutines don't emit `finished`, and the node is immediately reused by another coroutine.
+			first.bind_mut().kill();
+
+			let mut reused =
+				owner.coroutine(#[coroutine] || { yield frames(100); })
+					.pool(&pool)
+					.spawn();
+
+			if reused != first {
+				log_err("Expected the pooled node to be reused");
+			}
+
+			yield frames(2);
+
+			if !resumed.get() {
+				log_err("Expected `join_results` to resume once the awaited run ended, even though its node was reused");
+			}
+
+			reused.bind_mut().kill();
+
+			log("Test 92 finished");
+
+			test_93(node_ref);
+		});
+}
+
+
+fn test_93(node: Gd<Node>) {
+	log("Starting test 93");
+
+	let make = || {
+		#[coroutine] || {
+			yield frames(2);
+			1
+		}
+	};
+
+	let results = Rc::new(RefCell::new(Vec::new()));
+	let results_ref = results.clone();
+
+	let mut restartable =
+		node.coroutine(make())
+			.restartable(make)
+			.keep_alive(true)
+			.map_result(|result| (result.to::<i32>() * 10).to_variant())
+			.emit_on_kill((-1).to_variant())
+			.on_each_finish(move |result: i32| results_ref.borrow_mut().push(result))
+			.spawn();
+
+	let runs = Rc::new(Cell::new(0));
+	let runs_ref = runs.clone();
+	let make_service = move || {
+		let runs = runs_ref.clone();
+		#[coroutine] move || {
+			runs.set(runs.get() + 1);
+			yield frames(1);
+		}
+	};
+
+	let mut service =
+		node.coroutine(make_service())
+			.restart_on_panic(1, make_service)
+			.keep_alive(true)
+			.spawn();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(4);
+
+			restartable.bind_mut().restart();
+			yield frames(1);
+			restartable.bind_mut().kill();
+
+			restartable.bind_mut().restart();
+			yield frames(4);
+
+			if *results.borrow() != [10, -10, 10] {
+				log_err(format!("Expected `map_result` and `emit_on_kill` to apply to every run, results: {:?}", results.borrow()));
+			}
+
+			service.bind_mut().restart();
+			yield frames(3);
+
+			if runs.get() != 2 || !service.is_finished() {
+				log_err(format!("Expected `restart_on_panic`'s factory to also be used by `restart`, runs: {}", runs.get()));
+			}
+
+			restartable.queue_free();
+			service.queue_free();
+
+			log("Test 93 finished");
+
+			test_94(node_ref);
+		});
+}
+
+
+fn test_94(node: Gd<Node>) {
+	log("Starting test 94");
+
+	let result = Rc::new(Cell::new(None));
+	let result_ref = result.clone();
+
+	let finished =
+		node.coroutine(
+			#[coroutine] || {
+				if false {
+					yield frames(1);
+				}
+
+				94
+			})
+			.start_mode(CoroutineStartMode::Immediate)
+			.on_finish(move |value: i32| result_ref.set(Some(value)))
+			.spawn();
+
+	if result.get() != Some(94) {
+		log_err(format!("Expected `on_finish` to observe a coroutine that finished during `spawn`, got: {:?}", result.get()));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(2);
+
+			if finished.is_instance_valid() {
+				log_err("Expected the coroutine that finished during `spawn` to be freed by now.");
+			}
+
+			if !finished.is_finished() || finished.is_running() {
+				log_err("Expected the freed coroutine to still count as finished.");
+			}
+
+			// Waiting on the freed node resumes right away, instead of panicking.
+			yield finished.wait_until_finished();
+
+			log("Test 94 finished");
+
+			test_95(node_ref);
+		});
+}
+
+
+fn test_95(node: Gd<Node>) {
+	log("Starting test 95");
+
+	let pool = CoroutinePool::new(1);
+
+	let mut named =
+		node.coroutine(#[coroutine] || { yield frames(100); })
+			.pool(&pool)
+			.named("PooledDoor")
+			.spawn();
+
+	named.bind_mut().kill();
+
+	let mut reused =
+		node.coroutine(#[coroutine] || { yield frames(100); })
+			.pool(&pool)
+			.spawn();
+
+	if reused != named {
+		log_err("Expected the pooled node to be reused");
+	}
+
+	if reused.get_name().to_string().contains("PooledDoor") {
+		log_err(format!("Expected the reused node not to keep its previous custom name, got: {}", reused.get_name()));
+	}
+
+	reused.bind_mut().kill();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+			log("Test 95 finished");
+
+			test_96(node_ref);
+		});
+}
+
+
+fn test_96(node: Gd<Node>) {
+	log("Starting test 96");
+
+	let done = Arc::new(AtomicBool::new(false));
+	let result = Rc::new(Cell::new(None));
+
+	let mut task = {
+		let done = done.clone();
+		let result = result.clone();
+
+		node.async_task(async move {
+				smol::Timer::after(Duration::from_millis(20)).await;
+				done.store(true, Ordering::Relaxed);
+				96
+			})
+			.on_finish(move |value: i32| result.set(Some(value)))
+			.spawn_paused()
+	};
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			// The task keeps running in the background while its coroutine is paused,
+			// so its result is only taken once the task is long finished.
+			let done_ref = done.clone();
+			yield wait_until(move || done_ref.load(Ordering::Relaxed));
+			yield real_seconds(0.05);
+
+			task.bind_mut().start();
+			yield frames(1);
+
+			if result.get() != Some(96) || !task.is_finished() {
+				log_err(format!("Expected the finished task's result to be taken on its first poll, got: {:?}", result.get()));
+			}
+
+			log("Test 96 finished");
+
+			test_97(node_ref);
+		});
+}
+
+
+fn test_97(node: Gd<Node>) {
+	log("Starting test 97");
+
+	let elapsed = Rc::new(RefCell::new(Vec::new()));
+
+	let mut task =
+		node.async_task(async {
+				smol::Timer::after(Duration::from_millis(100)).await;
+			})
+			.emit_waiting(true)
+			.keep_alive(true)
+			.spawn();
+
+	{
+		let elapsed = elapsed.clone();
+
+		task.connect(SIGNAL_WAITING, &Callable::from_local_fn("test_97_waiting", move |args| {
+			elapsed.borrow_mut().push(args[0].to::<f64>());
+			Ok(Variant::nil())
+		}));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield task.wait_until_finished();
+
+			let elapsed = elapsed.borrow();
+
+			if elapsed.is_empty() || elapsed.windows(2).any(|pair| pair[1] < pair[0]) {
+				log_err(format!("Expected `waiting` to be emitted with increasing elapsed seconds, got: {elapsed:?}"));
+			}
+
+			if task.bind().get_elapsed_seconds() < elapsed.last().copied().unwrap_or_default() {
+				log_err(format!("Expected the elapsed seconds to be kept after finishing, got: {}", task.bind().get_elapsed_seconds()));
+			}
+
+			task.queue_free();
+
+			log("Test 97 finished");
+
+			test_98(node_ref);
+		});
+}
+
+
+fn test_98(node: Gd<Node>) {
+	log("Starting test 98");
+
+	let mut popup = Node::new_alloc();
+	node.clone().add_child(&popup);
+
+	let resumed = Rc::new(Cell::new(false));
+	let resumed_ref = resumed.clone();
+	let waited = popup.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield wait_until_freed(waited);
+			resumed_ref.set(true);
+		});
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(2);
+
+			if resumed.get() {
+				log_err("Expected `wait_until_freed` to keep waiting while the node is alive.");
+			}
+
+			popup.queue_free();
+
+			if resumed.get() || !popup.is_instance_valid() {
+				log_err("Expected a node queued for deletion to still be awaited until it's actually freed.");
+			}
+
+			yield frames(2);
+
+			if !resumed.get() {
+				log_err("Expected `wait_until_freed` to resume once the node was freed.");
+			}
+
+			log("Test 98 finished");
+
+			test_99(node_ref);
+		});
+}
+
+
+fn test_99(node: Gd<Node>) {
+	log("Starting test 99");
+
+	let count_steps = |steps: Rc<Cell<u32>>| {
+		#[coroutine] move || {
+			for _ in 0..10 {
+				steps.set(steps.get() + 1);
+				yield frames(0);
+			}
+		}
+	};
+
+	let limited_steps = Rc::new(Cell::new(0));
+	node.coroutine(count_steps(limited_steps.clone()))
+		.max_steps_per_frame(3)
+		.spawn();
+
+	let unlimited_steps = Rc::new(Cell::new(0));
+	node.start_coroutine(count_steps(unlimited_steps.clone()));
+
+	// Spawned last, so it's polled after both coroutines on every frame.
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			if limited_steps.get() != 3 || unlimited_steps.get() != 10 {
+				log_err(format!("Expected 3 limited and 10 unlimited steps on the first poll, got: {} and {}",
+					limited_steps.get(), unlimited_steps.get()));
+			}
+
+			yield frames(1);
+
+			if limited_steps.get() != 6 {
+				log_err(format!("Expected the limited coroutine to continue on the next poll, steps: {}", limited_steps.get()));
+			}
+
+			log("Test 99 finished");
+
+			test_100(node_ref);
+		});
+}
+
+
+fn test_100(node: Gd<Node>) {
+	log("Starting test 100");
+
+	let chunks_done = Rc::new(Cell::new(0));
+	let chunks_ref = chunks_done.clone();
+
+	let work =
+		node.start_coroutine(
+			budgeted_work(5.0, move || {
+				std::thread::sleep(Duration::from_millis(1));
+				chunks_ref.set(chunks_ref.get() + 1);
+				chunks_ref.get() < 30
+			}));
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			let first_frame = chunks_done.get();
+
+			if first_frame == 0 || first_frame >= 30 {
+				log_err(format!("Expected the first frame to do part of the work within its budget, chunks: {first_frame}"));
+			}
+
+			yield work.wait_until_finished();
+
+			if chunks_done.get() != 30 {
+				log_err(format!("Expected `budgeted_work` to finish once `f` returned false, chunks: {}", chunks_done.get()));
+			}
+
+			log("Test 100 finished");
+
+			test_101(node_ref);
+		});
+}
+
+
+fn test_101(node: Gd<Node>) {
+	log("Starting test 101");
+
+	let greeting = String::from("hello");
+	let result = Rc::new(RefCell::new(String::new()));
+	let result_ref = result.clone();
+
+	// `spire!` captures by move, so `greeting` is owned by the closure.
+	let greet =
+		node.coroutine(spire! {
+				yield frames(1);
+				format!("{greeting}, world")
+			})
+			.on_finish(move |message: String| *result_ref.borrow_mut() = message)
+			.spawn();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield greet.wait_until_finished();
+
+			if *result.borrow() != "hello, world" {
+				log_err(format!("Expected the `spire!` closure to run and return its result, got: {:?}", result.borrow()));
+			}
+
+			log("Test 101 finished");
+
+			test_102(node_ref);
+		});
+}
+
+
+fn test_102(node: Gd<Node>) {
+	log("Starting test 102");
+
+	set_default_process_priority(7);
+	set_default_physics_priority(8);
+
+	let defaulted = node.start_coroutine(#[coroutine] || { yield frames(1); });
+
+	let overridden =
+		node.coroutine(#[coroutine] || { yield frames(1); })
+			.process_priority(3)
+			.spawn();
+
+	set_default_process_priority(256);
+	set_default_physics_priority(256);
+
+	let restored = node.start_coroutine(#[coroutine] || { yield frames(1); });
+
+	if defaulted.get_process_priority() != 7 || defaulted.get_physics_process_priority() != 8 {
+		log_err(format!("Expected the global default priorities, got: {} and {}",
+			defaulted.get_process_priority(), defaulted.get_physics_process_priority()));
+	}
+
+	if overridden.get_process_priority() != 3 || overridden.get_physics_process_priority() != 8 {
+		log_err(format!("Expected the builder's priority to override the global default, got: {} and {}",
+			overridden.get_process_priority(), overridden.get_physics_process_priority()));
+	}
+
+	if restored.get_process_priority() != 256 || restored.get_physics_process_priority() != 256 {
+		log_err("Expected coroutines spawned after restoring the global default to use it.");
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(2);
+			log("Test 102 finished");
+
+			test_103(node_ref);
+		});
+}
+
+
+fn test_103(node: Gd<Node>) {
+	log("Starting test 103");
+
+	let slow = node.start_coroutine(#[coroutine] || { yield frames(4); 1 });
+	let fast = node.start_coroutine(#[coroutine] || { yield frames(1); 2 });
+	let mut killed = node.start_coroutine(#[coroutine] || { yield frames(100); 3 });
+
+	let (wait, results) = join_results([slow.clone(), fast, killed.clone()]);
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(2);
+			killed.bind_mut().kill();
+
+			yield wait;
+
+			if !slow.is_finished() {
+				log_err("Expected `join_results` to wait for the slowest coroutine.");
+			}
+
+			if *results.borrow() != [1.to_variant(), 2.to_variant(), Variant::nil()] {
+				log_err(format!("Expected the results in the order of the coroutines, with nil for the killed one, got: {:?}",
+					results.borrow()));
+			}
+
+			log("Test 103 finished");
+
+			test_104(node_ref);
+		});
+}
+
+
+fn test_104(node: Gd<Node>) {
+	log("Starting test 104");
+
+	let typed_results = Rc::new(RefCell::new(Vec::new()));
+	let signal_results = Rc::new(RefCell::new(Vec::new()));
+
+	let spawn_typed = |typed_results: Rc<RefCell<Vec<i32>>>, signal_results: Rc<RefCell<Vec<Variant>>>| {
+		let mut coroutine =
+			node.coroutine(#[coroutine] || { yield frames(100); 0 })
+				.on_finish(move |result: i32| typed_results.borrow_mut().push(result))
+				.spawn();
+
+		coroutine.connect(SIGNAL_FINISHED, &Callable::from_local_fn("test_104_finished", move |args| {
+			signal_results.borrow_mut().push(args[0].clone());
+			Ok(Variant::nil())
+		}));
+
+		coroutine
+	};
+
+	let mut matching = spawn_typed(typed_results.clone(), signal_results.clone());
+	let mut mismatched = spawn_typed(typed_results.clone(), signal_results.clone());
+
+	matching.bind_mut().finish_with(5.to_variant());
+
+	// Logs a warning naming the expected type, but the coroutine still finishes with the given result.
+	mismatched.bind_mut().finish_with("five".to_variant());
+
+	if !matching.is_finished() || !mismatched.is_finished() {
+		log_err("Expected `finish_with` to finish the coroutine regardless of the result's type.");
+	}
+
+	if *typed_results.borrow() != [5] {
+		log_err(format!("Expected only the matching result to reach the typed callbacks, got: {:?}", typed_results.borrow()));
+	}
+
+	if *signal_results.borrow() != [5.to_variant(), "five".to_variant()] {
+		log_err(format!("Expected `finished` to be emitted with both results, got: {:?}", signal_results.borrow()));
+	}
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+			log("Test 104 finished");
+
+			test_105(node_ref);
+		});
+}
+
+
+fn test_105(node: Gd<Node>) {
+	log("Starting test 105");
+
+	let mut entity = Node::new_alloc();
+	node.clone().add_child(&entity);
+
+	let steps = Rc::new(Cell::new(0));
+	let steps_ref = steps.clone();
+
+	let mut effect =
+		entity.start_coroutine(
+			#[coroutine] move || {
+				for _ in 0..4 {
+					steps_ref.set(steps_ref.get() + 1);
+					yield frames(1);
+				}
+			});
+
+	let mut self_detaching =
+		entity.start_coroutine(
+			#[coroutine] || {
+				yield frames(1);
+				SpireCoroutine::current().unwrap().bind_mut().detach_to_root();
+				yield frames(100);
+			});
+
+	let root = node.get_tree().unwrap().get_root().unwrap().upcast::<Node>();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield frames(1);
+
+			effect.bind_mut().detach_to_root();
+
+			if effect.get_parent() != Some(root.clone()) {
+				log_err("Expected `detach_to_root` to move the coroutine to the tree's root.");
+			}
+
+			entity.queue_free();
+			yield frames(1);
+
+			if self_detaching.get_parent() != Some(root.clone()) {
+				log_err("Expected `detach_to_root` to be deferred when called from inside the coroutine.");
+			}
+
+			yield effect.wait_until_finished();
+
+			if steps.get() != 4 {
+				log_err(format!("Expected the detached coroutine to keep running after its previous parent was freed, steps: {}", steps.get()));
+			}
+
+			if !self_detaching.is_instance_valid() || self_detaching.is_finished() {
+				log_err("Expected the self-detached coroutine to survive its previous parent.");
+			}
+
+			self_detaching.bind_mut().kill();
+
+			log("Test 105 finished");
+
+			test_106(node_ref);
+		});
+}
+
+
+fn test_106(node: Gd<Node>) {
+	log("Starting test 106");
+
+	let continued = Rc::new(Cell::new(None));
+	let continued_ref = continued.clone();
+
+	let first =
+		node.coroutine(#[coroutine] || { yield frames(1); 7 })
+			.and_then_spawn(move |result| {
+				#[coroutine] move || {
+					yield frames(1);
+					continued_ref.set(Some(result * 2));
+				}
+			})
+			.spawn();
+
+	let doomed_owner = Node::new_alloc();
+	node.clone().add_child(&doomed_owner);
+
+	let orphaned = Rc::new(Cell::new(false));
+	let orphaned_ref = orphaned.clone();
+
+	let mut doomed =
+		doomed_owner.coroutine(#[coroutine] || { yield frames(100); 0 })
+			.and_then_spawn(move |_: i32| {
+				#[coroutine] move || {
+					orphaned_ref.set(true);
+					yield frames(1);
+				}
+			})
+			.spawn_on(node.clone());
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield first.wait_until_finished();
+
+			if continued.get().is_some() {
+				log_err("Expected the continuation to start only after the first coroutine finished.");
+			}
+
+			yield frames(4);
+
+			if continued.get() != Some(14) {
+				log_err(format!("Expected the continuation to receive the first coroutine's result, got: {:?}", continued.get()));
+			}
+
+			doomed_owner.free();
+			doomed.bind_mut().finish_with(0.to_variant());
+			yield frames(1);
+
+			if orphaned.get() {
+				log_err("Expected no continuation to be spawned once the owner was freed.");
+			}
+
+			log("Test 106 finished");
+
+			test_107(node_ref);
+		});
+}
+
+
+fn test_107(node: Gd<Node>) {
+	log("Starting test 107");
+
+	let mut settings = CoroutineSettings::new_gd();
+	settings.bind_mut().start_delay = 0.2;
+
+	let runs = Rc::new(Cell::new(0));
+	let runs_ref = runs.clone();
+	let make = move || {
+		let runs = runs_ref.clone();
+		#[coroutine] move || {
+			runs.set(runs.get() + 1);
+			yield frames(1);
+		}
+	};
+
+	let mut delayed =
+		node.coroutine(make())
+			.restartable(make)
+			.from_settings(&settings)
+			.keep_alive(true)
+			.spawn();
+
+	let node_ref = node.clone();
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield delayed.wait_until_finished();
+
+			if runs.get() != 1 {
+				log_err(format!("Expected the delayed coroutine to run once, runs: {}", runs.get()));
+			}
+
+			delayed.bind_mut().restart();
+			yield frames(2);
+
+			if runs.get() != 1 {
+				log_err("Expected the settings' start delay to also apply to restarted closures.");
+			}
+
+			yield seconds(0.3);
+
+			if runs.get() != 2 {
+				log_err(format!("Expected the restarted closure to run after the start delay, runs: {}", runs.get()));
+			}
+
+			delayed.queue_free();
+
+			log("Test 107 finished");
+
+			test_108(node_ref);
+		});
+}
+
+
+fn test_108(node: Gd<Node>) {
+	log("Starting test 108");
+
+	let mut freed = node.start_coroutine(#[coroutine] || { yield frames(100); 1 });
+	let alive = node.start_coroutine(#[coroutine] || { yield frames(1); 2 });
+
+	freed.bind_mut().kill();
+	freed.clone().free();
+
+	// Must neither panic on the freed coroutine nor wait on it.
+	let (wait, results) = join_results([freed, alive.clone()]);
+
+	node.start_coroutine(
+		#[coroutine] move || {
+			yield wait;
+
+			if !alive.is_finished() {
+				log_err("Expected `join_results` to wait for the coroutines that weren't freed.");
+			}
+
+			if *results.borrow() != [Variant::nil(), 2.to_variant()] {
+				log_err(format!("Expected nil for the coroutine freed before `join_results`, got: {:?}", results.borrow()));
+			}
+
+			log("Test 108 finished");
+
+			TestReporter::finish();
+		});
+}
+
+/// Compares the time spent spawning `count` individual coroutines against a single batch of `count` members.
+fn benchmark_spawn_many(count: i64) -> Yield {
+	let root = Engine::singleton()
+		.get_main_loop()
+		.unwrap()
+		.cast::<SceneTree>()
+		.get_root()
+		.unwrap();
+
+	let time = godot::classes::Time::singleton();
+
+	let start_usec = time.get_ticks_usec();
+	let individuals: Vec<Gd<SpireCoroutine>> =
+		(0..count)
+			.map(|_| root.start_coroutine(#[coroutine] || { yield frames(1); }))
+			.collect();
+	let individual_usec = time.get_ticks_usec() - start_usec;
+
+	let start_usec = time.get_ticks_usec();
+	let batch = root.spawn_many(0..count, |_| #[coroutine] || { yield frames(1); });
+	let batch_usec = time.get_ticks_usec() - start_usec;
+
+	log(format!("Spawning {count} coroutines: {individual_usec}us individually, {batch_usec}us as a batch"));
+
+	wait_until(move || batch.is_empty() && individuals.iter().all(|coroutine| coroutine.is_finished()))
+}
+
+fn seconds_accuracy_routine(label: &'static str) -> impl Unpin + Coroutine<(), Yield = Yield, Return = ()> {
+	#[coroutine] move || {
+		let time = godot::classes::Time::singleton();
+
+		let start_usec = time.get_ticks_usec();
+
+		for _ in 0..250 {
+			yield seconds(0.016);
+		}
+
+		let elapsed = (time.get_ticks_usec() - start_usec) as f64 / 1_000_000.0;
+		let expected = 250.0 * 0.016;
+
+		log(format!("{label}: Time passed after 250 yields of 0.016 seconds: {elapsed:.4}s, expected: {expected:.4}s"));
+
+		// Each yield carries over the excess time of the frame it ended on, so the total may only overshoot by a single frame.
+		if (elapsed - expected).abs() > 0.05 {
+			log_err(format!("{label}: Expected {expected:.4}s to have passed, got: {elapsed:.4}s"));
+		}
+	}
+}
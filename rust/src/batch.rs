@@ -0,0 +1,143 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use godot::prelude::*;
+
+use crate::prelude::*;
+use crate::yielding::SpireYield;
+
+/// A handle to the coroutines spawned by [spawn_many](StartCoroutine::spawn_many), which all run inside a single host [SpireCoroutine].
+///
+/// Since the members share a node, they are controlled as a unit: pausing, resuming or killing the batch affects every member.
+/// The host finishes once every member finished, with an [Array](VariantArray) of the members' results (in the order of the items) as its result.
+///
+/// Cloning the batch creates another handle to the same batch.
+#[derive(Clone)]
+pub struct CoroutineBatch {
+	host: Gd<SpireCoroutine>,
+	alive: Rc<Cell<usize>>,
+}
+
+impl CoroutineBatch {
+	pub(crate) fn new(host: Gd<SpireCoroutine>, alive: Rc<Cell<usize>>) -> Self {
+		Self { host, alive }
+	}
+
+	/// Returns the node hosting every member, e.g. for connecting to its [finished](SIGNAL_FINISHED) signal.
+	pub fn host(&self) -> Gd<SpireCoroutine> {
+		self.host.clone()
+	}
+
+	/// How many members haven't finished yet.
+	pub fn len(&self) -> usize {
+		if self.host.is_finished() {
+			0
+		} else {
+			self.alive.get()
+		}
+	}
+
+	/// Returns true if every member finished, or if the batch was killed.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// See [SpireCoroutine::pause]
+	pub fn pause(&self) {
+		if !self.host.is_finished() {
+			self.host.clone().bind_mut().pause();
+		}
+	}
+
+	/// See [SpireCoroutine::resume]
+	pub fn resume(&self) {
+		if !self.host.is_finished() {
+			self.host.clone().bind_mut().resume();
+		}
+	}
+
+	/// Kills every member that hasn't finished yet, see [SpireCoroutine::kill].
+	pub fn kill(&self) {
+		if !self.host.is_finished() {
+			self.host.clone().bind_mut().kill();
+		}
+	}
+
+	/// Coroutine resumes execution once every member finished.
+	pub fn wait_all(&self) -> SpireYield {
+		wait_all([self.host.clone()])
+	}
+}
+
+/// Creates the host's closure, which polls every member whenever the host is polled.
+///
+/// Also returns the counter of members that haven't finished yet.
+pub(crate) fn host_routine<T, C, R>(
+	items: impl IntoIterator<Item = T>,
+	mut make: impl FnMut(T) -> C,
+) -> (impl 'static + PollRoutine<Return = VariantArray>, Rc<Cell<usize>>)
+	where
+		C: 'static + PollRoutine<Return = R>,
+		R: 'static + ToGodot,
+{
+	let members: Vec<Option<CoroutineDriver>> =
+		items.into_iter()
+		     .map(|item| Some(CoroutineDriver::new(make(item))))
+		     .collect();
+
+	let alive = Rc::new(Cell::new(members.len()));
+	let results = Rc::new(RefCell::new(vec![Variant::nil(); members.len()]));
+
+	let runner = BatchRunner {
+		members,
+		results: results.clone(),
+		alive: alive.clone(),
+	};
+
+	let mut runner = Some(runner);
+
+	let routine =
+		state_machine(results, move |results| {
+			match runner.take() {
+				Some(runner) => Step::Wait(SpireYield::Dyn(Box::new(runner))),
+				None => Step::Done(results.take().into_iter().collect::<VariantArray>()),
+			}
+		});
+
+	(routine, alive)
+}
+
+struct BatchRunner {
+	members: Vec<Option<CoroutineDriver>>,
+	results: Rc<RefCell<Vec<Variant>>>,
+	alive: Rc<Cell<usize>>,
+}
+
+impl KeepWaiting for BatchRunner {
+	fn keep_waiting(&mut self, delta_time: f64) -> bool {
+		let mut alive = 0;
+
+		for (index, slot) in self.members.iter_mut().enumerate() {
+			let Some(member) = slot
+			else { continue };
+
+			// Members are driven like regular coroutines, those that panic or abort finish with `nil`.
+			match member.tick(delta_time) {
+				Some(result) => {
+					self.results.borrow_mut()[index] = result;
+					*slot = None;
+				}
+				None => {
+					alive += 1;
+				}
+			}
+		}
+
+		self.alive.set(alive);
+		alive > 0
+	}
+
+	fn describe(&self) -> Option<String> {
+		Some(format!("waiting on {} coroutines of a batch", self.alive.get()))
+	}
+}
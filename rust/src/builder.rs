@@ -1,29 +1,157 @@
-use std::ops::{Coroutine, CoroutineState};
-use std::pin::Pin;
+use std::cell::RefCell;
+#[cfg(feature = "async")]
+use std::panic::AssertUnwindSafe;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicI32, AtomicU64, Ordering};
+#[cfg(feature = "async")]
+use std::task::Poll;
 
 use godot::classes::node::ProcessMode;
 use godot::prelude::*;
+#[cfg(feature = "async")]
+use smol::future::FutureExt;
 
-use crate::OnFinishCall;
+use crate::{OnFinishCall, PanicRestart, ResultCheck};
 use crate::prelude::*;
+use crate::driver::Routine;
+use crate::routine::RoutineState;
 use crate::yielding::SpireYield;
 
+static DEFAULT_PROCESS_PRIORITY: AtomicI32 = AtomicI32::new(256);
+static DEFAULT_PHYSICS_PRIORITY: AtomicI32 = AtomicI32::new(256);
+static SPAWN_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Wraps `inner` in a coroutine that waits for `seconds` before resuming it, see [CoroutineBuilder::start_delay].
+fn delay_routine(inner: Routine, seconds: f64) -> Routine {
+	if seconds <= 0.0 {
+		return inner;
+	}
+
+	let mut delay = Some(crate::yielding::seconds(seconds));
+
+	let delayed =
+		state_machine(inner, move |inner| {
+			if let Some(delay) = delay.take() {
+				return Step::Wait(delay);
+			}
+
+			match inner.resume() {
+				RoutineState::Yielded(_yield) => Step::Wait(_yield),
+				RoutineState::Complete(result) => Step::Done(result),
+			}
+		});
+
+	Box::new(delayed)
+}
+
+/// Wraps `f` in a coroutine that converts its return value into a [Variant].
+pub(crate) fn into_routine<R: 'static + ToGodot>(
+	f: impl 'static + PollRoutine<Return = R>,
+) -> Routine {
+	let wrapper =
+		state_machine(f, |f| {
+			match f.resume() {
+				RoutineState::Yielded(_yield) => Step::Wait(_yield),
+				RoutineState::Complete(result) => Step::Done(result.to_variant()),
+			}
+		});
+
+	Box::new(wrapper)
+}
+
+/// Sets the [process priority](Node::set_process_priority) of every coroutine spawned from now on.
+///
+/// The default is 256, which makes coroutines run after most other nodes.
+pub fn set_default_process_priority(priority: i32) {
+	DEFAULT_PROCESS_PRIORITY.store(priority, Ordering::Relaxed);
+}
+
+/// Sets the [physics process priority](Node::set_physics_process_priority) of every coroutine spawned from now on.
+///
+/// The default is 256, which makes coroutines run after most other nodes.
+pub fn set_default_physics_priority(priority: i32) {
+	DEFAULT_PHYSICS_PRIORITY.store(priority, Ordering::Relaxed);
+}
+
+/// When a coroutine's first step runs, see [CoroutineBuilder::start_mode].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum CoroutineStartMode {
+	/// The first step runs on the coroutine's first [process](INode::process)/[physics_process](INode::physics_process).
+	#[default]
+	Deferred,
+	/// The first step runs synchronously in [spawn](CoroutineBuilder::spawn), right after the coroutine is added to the tree.
+	Immediate,
+}
+
 /// Builder struct for customizing coroutine behavior.
 #[must_use]
 pub struct CoroutineBuilder<R: 'static + ToGodot = ()> {
-	pub(crate) f: Box<dyn Unpin + Coroutine<(), Yield = SpireYield, Return = Variant>>,
+	pub(crate) f: Box<dyn PollRoutine<Return = Variant>>,
 	pub(crate) owner: Gd<Node>,
 	/// Determines if the coroutine should be polled in [_process](INode::process)
 	/// or [_physics_process](INode::physics_process)
 	pub(crate) poll_mode: PollMode,
 	/// Godot [ProcessMode] which the coroutine should run in.
 	pub(crate) process_mode: ProcessMode,
+	/// If set, overrides the [default process priority](set_default_process_priority).
+	pub(crate) process_priority: Option<i32>,
+	/// If set, overrides the [default physics priority](set_default_physics_priority).
+	pub(crate) physics_priority: Option<i32>,
 	/// Whether the coroutine should be started automatically.
 	pub(crate) auto_start: bool,
+	/// When the coroutine's first step runs, see [start_mode](Self::start_mode).
+	pub(crate) start_mode: CoroutineStartMode,
+	/// If set, the coroutine stays paused until this returns true.
+	pub(crate) start_gate: Option<Box<dyn FnMut() -> bool>>,
 	/// A list of callables to invoke when the coroutine finishes.
 	///
 	/// The callables will be invoked with the coroutine's return value as a Variant.
 	pub(crate) calls_on_finish: Vec<OnFinishCall>,
+	/// A list of closures to invoke with the delta time on every poll.
+	pub(crate) calls_on_each_frame: Vec<Box<dyn FnMut(f64)>>,
+	/// A list of closures to invoke if the coroutine is killed before finishing.
+	pub(crate) calls_on_cancel: Vec<Box<dyn FnOnce()>>,
+	/// A list of closures to invoke once the coroutine ends, regardless of how.
+	pub(crate) calls_on_exit: Vec<Box<dyn FnOnce()>>,
+	/// If set, the coroutine's closure is rebuilt after panicking, instead of the coroutine being killed.
+	pub(crate) panic_restart: Option<PanicRestart>,
+	/// If set, allows [restarting](SpireCoroutine::restart) the coroutine by rebuilding its closure.
+	///
+	/// Shared by [restartable](Self::restartable) and [restart_on_panic](Self::restart_on_panic).
+	pub(crate) restart_factory: Option<Box<dyn Fn() -> Routine>>,
+	/// If set, killing the coroutine finishes it with this result.
+	pub(crate) kill_result: Option<Variant>,
+	/// Set once a typed consumer of the result is registered, used to validate results passed to
+	/// [finish_with](SpireCoroutine::finish_with).
+	pub(crate) result_check: Option<ResultCheck>,
+	/// If set, transforms the coroutine's result before it's passed to the callbacks and the [finished](SIGNAL_FINISHED) signal.
+	pub(crate) result_map: Option<Box<dyn Fn(Variant) -> Variant>>,
+	/// Whether the coroutine should emit [waiting](SIGNAL_WAITING) on every poll that doesn't finish it.
+	pub(crate) emit_waiting: bool,
+	/// Multiplies the delta time the coroutine receives on every frame.
+	pub(crate) time_scale: f64,
+	/// Seconds to wait before the first step, applied to the closure and to the restart factory when spawning.
+	pub(crate) start_delay: f64,
+	/// How many times the coroutine's closure can be resumed in a single poll.
+	pub(crate) max_steps_per_frame: u32,
+	/// If set, a warning is logged when the coroutine waits on the same custom yield for this many polls.
+	pub(crate) stuck_threshold: Option<u64>,
+	/// If set, the coroutine's node is drawn from/returned to this pool.
+	pub(crate) pool: Option<CoroutinePool>,
+	/// If set, the coroutine's node joins this Godot group when spawning.
+	pub(crate) group: Option<StringName>,
+	/// If set, the coroutine's node is given this name when spawning.
+	pub(crate) name: Option<GString>,
+	/// Whether the coroutine's node stays alive after it ends, holding on to its result.
+	pub(crate) keep_alive: bool,
+	/// If set, every yield the coroutine processes is appended to it.
+	pub(crate) yield_records: Option<Rc<RefCell<Vec<YieldRecord>>>>,
+	/// If set, the coroutine is only polled while this node is processing.
+	pub(crate) mirrored_node: Option<Gd<Node>>,
+	/// Entries set as [metadata](Object::set_meta) on the coroutine's node when spawning.
+	pub(crate) metadata: Dictionary,
+	/// Whether `f` runs an async task.
+	pub(crate) is_async: bool,
 	/// Type hint for the coroutine's return value.
 	pub(crate) type_hint: std::marker::PhantomData<R>,
 }
@@ -36,32 +164,48 @@ impl<R> CoroutineBuilder<R>
 	#[doc(hidden)]
 	pub fn new_coroutine(
 		owner: Gd<Node>,
-		f: impl 'static + Unpin + Coroutine<(), Yield = SpireYield, Return = R>,
+		f: impl 'static + PollRoutine<Return = R>,
 	) -> CoroutineBuilder<R> {
-		let wrapper =
-			#[coroutine] move || {
-				let mut f = f;
-
-				loop {
-					let pin = Pin::new(&mut f);
-					match pin.resume(()) {
-						CoroutineState::Yielded(_yield) => {
-							yield _yield;
-						}
-						CoroutineState::Complete(result) => {
-							return result.to_variant();
-						}
-					}
-				}
-			};
+		Self::from_routine(owner, into_routine(f))
+	}
 
-		Self {
-			f: Box::new(wrapper),
+	/// Creates a builder with default settings, `f` must already convert its return value into a [Variant].
+	fn from_routine(
+		owner: Gd<Node>,
+		f: Box<dyn PollRoutine<Return = Variant>>,
+	) -> CoroutineBuilder<R> {
+		CoroutineBuilder {
+			f,
 			owner,
 			poll_mode: PollMode::Process,
 			process_mode: ProcessMode::INHERIT,
+			process_priority: None,
+			physics_priority: None,
 			auto_start: true,
+			start_mode: CoroutineStartMode::Deferred,
+			start_gate: None,
 			calls_on_finish: Vec::new(),
+			calls_on_each_frame: Vec::new(),
+			calls_on_cancel: Vec::new(),
+			calls_on_exit: Vec::new(),
+			panic_restart: None,
+			restart_factory: None,
+			kill_result: None,
+			result_check: None,
+			result_map: None,
+			stuck_threshold: None,
+			pool: None,
+			group: None,
+			name: None,
+			keep_alive: false,
+			yield_records: None,
+			mirrored_node: None,
+			metadata: Dictionary::new(),
+			is_async: false,
+			emit_waiting: false,
+			time_scale: 1.0,
+			start_delay: 0.0,
+			max_steps_per_frame: u32::MAX,
 			type_hint: std::marker::PhantomData,
 		}
 	}
@@ -78,59 +222,75 @@ impl<R> CoroutineBuilder<R>
 		where
 			R: Send,
 	{
-		let task = smol::spawn(f);
+		let task = smol::spawn(AssertUnwindSafe(f).catch_unwind());
+
+		let routine = state_machine(Some(task), |task| async_bridge::wait_for_task(task, |result| result.to_variant()));
+
+		Self {
+			is_async: true,
+			..Self::from_routine(owner, Box::new(routine))
+		}
+	}
+	
+	/// Creates a new coroutine builder with default settings.
+	///
+	/// Like [new_async_task](Self::new_async_task), but `f` is polled once on the calling thread in the coroutine's first step,
+	/// which [spawn](Self::spawn) runs synchronously since the builder starts in [CoroutineStartMode::Immediate].
+	/// If it's already finished, no background task is spawned and the coroutine completes synchronously in [spawn](Self::spawn).
+	#[cfg(feature = "async")]
+	#[doc(hidden)]
+	pub fn new_async_task_eager(
+		owner: Gd<Node>,
+		f: impl std::future::Future<Output = R> + Send + 'static,
+	) -> CoroutineBuilder<R>
+		where
+			R: Send,
+	{
+		let mut f = Some(Box::pin(AssertUnwindSafe(f).catch_unwind()));
 
 		let routine =
-			#[coroutine] move || {
-				while !task.is_finished() {
-					yield frames(1);
+			state_machine(None, move |task| {
+				// Only the first step polls `f`, which is then sent to the background if it isn't finished.
+				if let Some(mut f) = f.take() {
+					match async_bridge::poll_now(&mut f) {
+						Poll::Ready(result) => {
+							let result = async_bridge::unwrap_or_log(result);
+							return Step::Done(result.map_or_else(Variant::nil, |result| result.to_variant()));
+						}
+						Poll::Pending => *task = Some(smol::spawn(f)),
+					}
 				}
 
-				smol::block_on(task).to_variant()
-			};
+				async_bridge::wait_for_task(task, |result| result.to_variant())
+			});
 
-		CoroutineBuilder {
-			f: Box::new(routine),
-			owner,
-			poll_mode: PollMode::Process,
-			process_mode: ProcessMode::INHERIT,
-			auto_start: true,
-			calls_on_finish: Vec::new(),
-			type_hint: std::marker::PhantomData,
+		Self {
+			is_async: true,
+			start_mode: CoroutineStartMode::Immediate,
+			..Self::from_routine(owner, Box::new(routine))
 		}
 	}
-	
+
 	#[cfg(feature = "async")]
 	#[doc(hidden)]
 	pub unsafe fn new_async_task_unchecked(
 		owner: Gd<Node>,
 		f: impl std::future::Future<Output = R> + Unpin + 'static,
 	) -> CoroutineBuilder<R> {
-		let task = smol::spawn(pinky_promise::PinkyPromise(f));
+		let task = smol::spawn(AssertUnwindSafe(pinky_promise::PinkyPromise(f)).catch_unwind());
 		
-		let routine =
-			#[coroutine] move || {
-				while !task.is_finished() {
-					yield frames(1);
-				}
-				
-				smol::block_on(task).0.to_variant()
-			};
+		let routine = state_machine(Some(task), |task| async_bridge::wait_for_task(task, |result| result.0.to_variant()));
 		
-		CoroutineBuilder {
-			f: Box::new(routine),
-			owner,
-			poll_mode: PollMode::Process,
-			process_mode: ProcessMode::INHERIT,
-			auto_start: true,
-			calls_on_finish: Vec::new(),
-			type_hint: std::marker::PhantomData,
+		Self {
+			is_async: true,
+			..Self::from_routine(owner, Box::new(routine))
 		}
 	}
 
 	/// Whether the coroutine should be started automatically upon spawning.
 	///
-	/// If false, you'll have to manually call [SpireCoroutine::resume] after spawning.
+	/// If false, you'll have to manually call [SpireCoroutine::start] (or [resume](SpireCoroutine::resume)) after spawning, 
+	/// see also [spawn_paused](Self::spawn_paused).
 	pub fn auto_start(self, auto_start: bool) -> Self {
 		Self {
 			auto_start,
@@ -138,6 +298,83 @@ impl<R> CoroutineBuilder<R>
 		}
 	}
 
+	/// The coroutine stays paused until `gate` returns true, then it starts as usual.
+	///
+	/// `gate` is invoked on every [process](INode::process)/[physics_process](INode::physics_process) 
+	/// (depending on the [PollMode]) until it returns true, the coroutine is polled on that same frame.
+	///
+	/// Manually [resuming](SpireCoroutine::resume) the coroutine discards the gate.
+	/// Has no effect if [auto_start](Self::auto_start) is false.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use std::cell::Cell;
+	/// use std::rc::Rc;
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_start_when(node: Gd<Node2D>, level_loaded: Rc<Cell<bool>>) {
+	///     node.coroutine(
+	///         #[coroutine] || {
+	///             godot_print!("Level is loaded, spawning enemies!");
+	///             yield seconds(1.0);
+	///         })
+	///         .start_when(move || level_loaded.get())
+	///         .spawn();
+	/// }
+	/// ```
+	pub fn start_when(self, gate: impl 'static + FnMut() -> bool) -> Self {
+		Self {
+			start_gate: Some(Box::new(gate)),
+			..self
+		}
+	}
+
+	/// When the coroutine's first step runs, see [CoroutineStartMode].
+	///
+	/// By default ([CoroutineStartMode::Deferred]), the first step only runs on the coroutine's first [process](INode::process)/[physics_process](INode::physics_process).
+	/// With [CoroutineStartMode::Immediate], it runs synchronously in [spawn](Self::spawn), right after the coroutine is added to the tree,
+	/// which is useful for coroutines spawned in [ready](INode::ready) that need to set up state before the first frame is drawn.
+	///
+	/// The immediate step counts as the coroutine's first poll, with a delta time of zero.
+	/// It has no effect if the coroutine starts paused, see [auto_start](Self::auto_start) and [start_when](Self::start_when).
+	///
+	/// Keep in mind that:
+	/// - The coroutine may finish during the immediate step, in which case [finished](SIGNAL_FINISHED) is emitted
+	///   before [spawn](Self::spawn) returns, so only callbacks registered through the builder (e.g. [on_finish](Self::on_finish)) observe it.
+	///   The returned node is then already [queued for deletion](Node::queue_free): it stays valid until the end of the frame,
+	///   and [is_finished](IsFinished::is_finished) keeps returning true after it's freed.
+	/// - If the caller is holding a `bind_mut` guard of an object that the coroutine binds during its first step
+	///   (e.g. spawning from `&mut self` in [ready](INode::ready)), the bind will panic.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_start_mode(node: Gd<Node>, mut bullet: Gd<Node2D>) {
+	///     node.coroutine(
+	///         #[coroutine] move || {
+	///             // Runs before `spawn` returns, so the bullet is never drawn at the origin.
+	///             bullet.set_position(Vector2::new(100.0, 0.0));
+	///             yield seconds(1.0);
+	///             bullet.queue_free();
+	///         })
+	///         .start_mode(CoroutineStartMode::Immediate)
+	///         .spawn();
+	/// }
+	/// ```
+	pub fn start_mode(self, start_mode: CoroutineStartMode) -> Self {
+		Self {
+			start_mode,
+			..self
+		}
+	}
+
 	/// Godot [ProcessMode] which the coroutine should run in.
 	pub fn process_mode(self, process_mode: ProcessMode) -> Self {
 		Self {
@@ -155,17 +392,10 @@ impl<R> CoroutineBuilder<R>
 		}
 	}
 
-	/// Adds `f` to the list of closures that will be invoked when the coroutine finishes.
+	/// The coroutine's [process priority](Node::set_process_priority), lower values are processed first.
 	///
-	/// The return value of the coroutine(`T`) will be passed to `f`.
-	/// 
-	/// [finished](SIGNAL_FINISHED) is only emitted if the coroutine finishes normally.
-	/// 
-	/// Some cases where the coroutine's end is considered "abnormal":
-	/// - The parent node of the coroutine was deleted (freed)
-	/// - The coroutine's main closure panics
-	/// - The coroutine ends with [force_run_to_completion](SpireCoroutine::force_run_to_completion)
-	/// - The coroutine ends with [kill](SpireCoroutine::kill)
+	/// Only affects coroutines polled on [PollMode::Process].
+	/// Defaults to the value set with [set_default_process_priority], which is 256 unless changed.
 	///
 	/// # Example
 	///
@@ -174,64 +404,67 @@ impl<R> CoroutineBuilder<R>
 	/// use godot::prelude::*;
 	/// use gdext_coroutines::prelude::*;
 	///
-	/// fn showcase_finish(node: Gd<Node2D>) {
+	/// fn showcase_process_priority(node: Gd<Node2D>) {
 	///     node.coroutine(
-	///         #[coroutine] || { 
-	///             yield frames(2);
-	///             return 5;
+	///         #[coroutine] || {
+	///             for _ in 0..10 {
+	///                 // Runs before nodes with the default priority (0) read the state it updates.
+	///                 yield frames(1);
+	///             }
 	///         })
-	///         .on_finish(|res| println!("Coroutine finished, result: {res}"))
+	///         .process_priority(-10)
 	///         .spawn();
 	/// }
 	/// ```
-	pub fn on_finish(
-		self,
-		f: impl 'static + FnOnce(R),
-	) -> Self
-		where
-			R: FromGodot,
-	{
-		let wrapper =
-			move |var: Variant| {
-				match var.try_to::<R>() {
-					Ok(r) => { f(r); }
-					Err(err) => {
-						godot_error!("{err}");
-					}
-				}
-			};
+	pub fn process_priority(self, priority: i32) -> Self {
+		Self {
+			process_priority: Some(priority),
+			..self
+		}
+	}
 
-		let mut calls_on_finish = self.calls_on_finish;
-		calls_on_finish.push(OnFinishCall::Closure(Box::new(wrapper)));
+	/// Same as [process_priority](Self::process_priority), but for coroutines polled on [PollMode::Physics].
+	///
+	/// Defaults to the value set with [set_default_physics_priority], which is 256 unless changed.
+	pub fn physics_process_priority(self, priority: i32) -> Self {
+		Self {
+			physics_priority: Some(priority),
+			..self
+		}
+	}
 
+	/// Multiplies the delta time the coroutine receives on every frame, which speeds up (or slows down) its [seconds] waits.
+	///
+	/// Does not affect [frames], [real_seconds] or [physics_seconds]. Negative values are clamped to 0, which freezes [seconds] waits.
+	///
+	/// Can be changed after spawning with [SpireCoroutine::set_time_scale].
+	pub fn time_scale(self, time_scale: f64) -> Self {
 		Self {
-			calls_on_finish,
+			time_scale: time_scale.max(0.0),
 			..self
 		}
 	}
 
-	/// See [on_finish](SpireCoroutine::on_finish)
-	/// 
-	/// This variant takes a [Callable] instead of a closure.
-	pub fn on_finish_callable(
-		self,
-		callable: Callable,
-	) -> Self
-		where
-			R: FromGodot,
-	{
-		let mut calls_on_finish = self.calls_on_finish;
-		calls_on_finish.push(OnFinishCall::Callable(callable));
+	/// Delays the coroutine's first step by `seconds`, as if its closure started with `yield seconds(delay)`.
+	///
+	/// The delay is affected by [time_scale](Self::time_scale), and does nothing if `seconds` isn't positive.
+	/// Calling it multiple times adds up the delays.
+	///
+	/// Closures rebuilt by [restartable](Self::restartable) or [restart_on_panic](Self::restart_on_panic) are delayed as well.
+	pub fn start_delay(self, seconds: f64) -> Self {
+		if seconds <= 0.0 {
+			return self;
+		}
 
 		Self {
-			calls_on_finish,
+			start_delay: self.start_delay + seconds,
 			..self
 		}
 	}
 
-	/// Completes the builder, spawning the coroutine's executor.
+	/// Applies the settings stored in a [CoroutineSettings] resource: poll mode, process mode, start delay and time scale.
 	///
-	/// The executor is the type [SpireCoroutine], a node that will be added as a child of `owner`.
+	/// Settings applied afterwards override the resource's, except for the start delay, which adds up with [start_delay](Self::start_delay).
 	///
 	/// # Example
 	///
@@ -240,39 +473,1076 @@ impl<R> CoroutineBuilder<R>
 	/// use godot::prelude::*;
 	/// use gdext_coroutines::prelude::*;
 	///
-	/// fn showcase_spawn(node: Gd<Node2D>) {
-	///     let builder = 
-	///         node.coroutine(
-	///             #[coroutine] || {
-	///                 yield frames(5);
-	///                 yield seconds(5.0);
-	///             });
+	/// #[derive(GodotClass)]
+	/// #[class(init, base = Node2D)]
+	/// struct Enemy {
+	///     #[export]
+	///     attack_settings: Option<Gd<CoroutineSettings>>,
+	///     base: Base<Node2D>,
+	/// }
 	///
-	///     let coroutine: Gd<SpireCoroutine> = builder.spawn();
+	/// impl Enemy {
+	///     fn attack(&mut self) {
+	///         let mut builder =
+	///             self.coroutine(
+	///                 #[coroutine] || {
+	///                     yield seconds(1.5);
+	///                 });
+	///
+	///         if let Some(settings) = &self.attack_settings {
+	///             builder = builder.from_settings(settings);
+	///         }
+	///
+	///         builder.spawn();
+	///     }
 	/// }
 	/// ```
-	pub fn spawn(self) -> Gd<SpireCoroutine> {
-		let mut coroutine =
-			Gd::from_init_fn(|base| {
-				SpireCoroutine {
-					base,
-					coroutine: self.f,
-					poll_mode: self.poll_mode,
-					last_yield: None,
-					paused: !self.auto_start,
-					calls_on_finish: self.calls_on_finish,
+	pub fn from_settings(self, settings: &Gd<CoroutineSettings>) -> Self {
+		let settings = settings.bind();
+
+		Self {
+			poll_mode: settings.poll_mode,
+			process_mode: settings.to_process_mode(),
+			..self
+		}
+		.time_scale(settings.time_scale)
+		.start_delay(settings.start_delay)
+	}
+
+	/// Whether the coroutine should emit [waiting](SIGNAL_WAITING) on every poll that doesn't finish it.
+	///
+	/// The signal's argument is the amount of seconds the coroutine has been running for,
+	/// which can be used to show progress indicators for long async tasks.
+	///
+	/// Regardless of this setting, the elapsed time can be queried with [SpireCoroutine::get_elapsed_seconds].
+	pub fn emit_waiting(self, emit_waiting: bool) -> Self {
+		Self {
+			emit_waiting,
+			..self
+		}
+	}
+
+	/// How many times the coroutine's closure can be resumed in a single poll.
+	///
+	/// Whenever a yield is already satisfied when polled (e.g. `frames(0)`, or a [wait_until] whose condition is true),
+	/// the closure is resumed again in the same poll. By default, this repeats until the coroutine
+	/// parks on a wait that isn't satisfied yet, or finishes.
+	///
+	/// Setting a limit spreads those immediate continuations across frames: once the closure was resumed `max_steps` times,
+	/// the coroutine stops for the current frame and continues on the next poll.
+	///
+	/// Values below 1 are treated as 1.
+	pub fn max_steps_per_frame(self, max_steps: u32) -> Self {
+		Self {
+			max_steps_per_frame: max_steps.max(1),
+			..self
+		}
+	}
+
+	/// Transforms every yield the coroutine produces with `map`, before the coroutine starts waiting on it.
+	///
+	/// Can be called multiple times, the mappings are applied in the order they were added.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// // "Fast debug mode": every wait in seconds is 10 times shorter.
+	/// fn showcase_map_yield(node: Gd<Node2D>) {
+	///     node.coroutine(
+	///         #[coroutine] || {
+	///             yield seconds(10.0);
+	///         })
+	///         .map_yield(|_yield| {
+	///             match _yield {
+	///                 Yield::Seconds(secs) => seconds(secs / 10.0),
+	///                 other => other,
+	///             }
+	///         })
+	///         .spawn();
+	/// }
+	/// ```
+	pub fn map_yield(self, mut map: impl 'static + FnMut(SpireYield) -> SpireYield) -> Self {
+		let mapped =
+			state_machine(self.f, move |inner| {
+				match inner.resume() {
+					RoutineState::Yielded(_yield) => Step::Wait(map(_yield)),
+					RoutineState::Complete(result) => Step::Done(result),
 				}
 			});
 
-		coroutine.set_process_priority(256);
-		coroutine.set_physics_process_priority(256);
-
-		coroutine.set_process_mode(self.process_mode);
+		Self {
+			f: Box::new(mapped),
+			..self
+		}
+	}
 
-		let mut owner = self.owner;
-		owner.add_child(&coroutine);
+	/// Transforms the coroutine's result with `map` before it reaches any consumer:
+	/// the callbacks registered with [on_finish](Self::on_finish) and similar, and the [finished](SIGNAL_FINISHED) signal.
+	///
+	/// Also applies to results passed to [finish_with](SpireCoroutine::finish_with).
+	///
+	/// Can be called multiple times, the mappings are applied in the order they were added.
+	/// The mappings are kept after the coroutine finishes, so they also apply to the runs started by [restart](SpireCoroutine::restart).
+	///
+	/// Note that typed consumers (e.g. [on_finish](Self::on_finish)) still expect the mapped result to be convertible into `R`,
+	/// prefer [on_finish_callable](Self::on_finish_callable) or the [finished](SIGNAL_FINISHED) signal if `map` changes its type.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::classes::Engine;
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// // Wraps the result in an envelope with metadata, for every listener of `finished`.
+	/// fn showcase_map_result(node: Gd<Node2D>) {
+	///     node.coroutine(
+	///         #[coroutine] || {
+	///             yield frames(2);
+	///             5
+	///         })
+	///         .map_result(|result| {
+	///             let mut envelope = Dictionary::new();
+	///             envelope.set("result", result);
+	///             envelope.set("finished_at_frame", Engine::singleton().get_process_frames());
+	///             envelope.to_variant()
+	///         })
+	///         .spawn();
+	/// }
+	/// ```
+	pub fn map_result(self, map: impl 'static + Fn(Variant) -> Variant) -> Self {
+		let result_map: Box<dyn Fn(Variant) -> Variant> =
+			match self.result_map {
+				Some(previous) => Box::new(move |result| map(previous(result))),
+				None => Box::new(map),
+			};
 
-		coroutine
+		Self {
+			result_map: Some(result_map),
+			..self
+		}
+	}
+
+	/// Logs a warning once the coroutine has been waiting on the same custom yield
+	/// (e.g. [wait_until], [wait_while], [KeepWaiting] implementations) for `polls` consecutive polls.
+	///
+	/// Helps catching conditions that can never become true during development, see [SpireCoroutine::is_possibly_stuck].
+	pub fn stuck_threshold(self, polls: u64) -> Self {
+		Self {
+			stuck_threshold: Some(polls.max(1)),
+			..self
+		}
+	}
+
+	/// Reuses nodes from `pool` instead of allocating a new one, and returns the node to `pool` once the coroutine ends.
+	///
+	/// See [CoroutinePool] for caveats about handles of pooled coroutines.
+	pub fn pool(self, pool: &CoroutinePool) -> Self {
+		Self {
+			pool: Some(pool.clone()),
+			..self
+		}
+	}
+
+	/// Attaches the entries of `data` to the coroutine's node as [metadata](Object::set_meta), keys are converted to strings.
+	///
+	/// Can be called multiple times, later entries overwrite earlier ones with the same key.
+	///
+	/// Useful for tagging coroutines with information that tools can read back with [SpireCoroutine::get_metadata],
+	/// e.g. a category, source location or owner.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_with_metadata(node: Gd<Node2D>) {
+	///     node.coroutine(
+	///         #[coroutine] || {
+	///             yield seconds(1.0);
+	///         })
+	///         .with_metadata(dict! { "category": "ai", "source": "enemy.rs" })
+	///         .spawn();
+	/// }
+	/// ```
+	pub fn with_metadata(self, data: Dictionary) -> Self {
+		let mut metadata = self.metadata;
+		metadata.extend_dictionary(&data, true);
+
+		Self {
+			metadata,
+			..self
+		}
+	}
+
+	/// Whether the coroutine's node should stay alive once the coroutine ends, instead of being freed.
+	///
+	/// The node stays as a child of its owner (and is freed along with it), but stops processing.
+	/// Its result can then be read at any time with [SpireCoroutine::try_get_result], which suits polling-style consumers, 
+	/// e.g. UI code that checks [is_result_ready](SpireCoroutine::is_result_ready) every frame.
+	///
+	/// Takes precedence over [pool](Self::pool): kept alive nodes are never recycled. Free the node manually once it's no longer needed.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_keep_alive(node: Gd<Node2D>) -> Gd<SpireCoroutine> {
+	///     node.coroutine(
+	///         #[coroutine] || {
+	///             yield seconds(1.0);
+	///             "Loaded!"
+	///         })
+	///         .keep_alive(true)
+	///         .spawn()
+	/// }
+	///
+	/// fn poll_loading(loading: &Gd<SpireCoroutine>) {
+	///     if loading.bind().is_result_ready() {
+	///         godot_print!("{}", loading.bind().try_get_result());
+	///     }
+	/// }
+	/// ```
+	pub fn keep_alive(self, keep_alive: bool) -> Self {
+		Self {
+			keep_alive,
+			..self
+		}
+	}
+
+	/// Appends a [YieldRecord] to `sink` for every yield the coroutine processes, in the order they were processed.
+	///
+	/// Records hold the yield's kind and duration along with when it happened (relative to the coroutine's start),
+	/// which makes traces of different runs comparable, e.g. for diagnosing timing bugs or for a replay system.
+	///
+	/// Yields are recorded after [map_yield](Self::map_yield) transformed them. Calling this again replaces the previous sink.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use std::cell::RefCell;
+	/// use std::rc::Rc;
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_record_yields(node: Gd<Node2D>) -> Rc<RefCell<Vec<YieldRecord>>> {
+	///     let trace = Rc::new(RefCell::new(Vec::new()));
+	///
+	///     node.coroutine(
+	///         #[coroutine] || {
+	///             yield frames(2);
+	///             yield seconds(0.5);
+	///         })
+	///         .record_yields(trace.clone())
+	///         .spawn();
+	///
+	///     trace
+	/// }
+	/// ```
+	pub fn record_yields(self, sink: Rc<RefCell<Vec<YieldRecord>>>) -> Self {
+		Self {
+			yield_records: Some(sink),
+			..self
+		}
+	}
+
+	/// Ties the coroutine's active state to `owner`'s processing state: the coroutine is only polled on frames where
+	/// `owner` [is processing](Node::is_processing) (or [physics processing](Node::is_physics_processing),
+	/// depending on the [PollMode]) and [can process](Node::can_process).
+	///
+	/// Skipped frames don't advance the coroutine's time, so waits resume where they left off once `owner` processes again.
+	/// Useful for entities that get disabled by turning their processing off, e.g. an inactive enemy's AI.
+	///
+	/// `owner` doesn't need to be the node the coroutine is spawned on. Note that nodes only process if they implement
+	/// `_process` (`_physics_process`) or had [set_process](Node::set_process) called, mirroring a node that never processes
+	/// keeps the coroutine from ever running. If `owner` is freed, the coroutine runs normally.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_mirror_owner_processing(mut enemy: Gd<Node2D>) {
+	///     enemy.coroutine(
+	///         #[coroutine] || {
+	///             for _ in 0..10 {
+	///                 yield seconds(1.0);
+	///                 godot_print!("Patrolling...");
+	///             }
+	///         })
+	///         .mirror_owner_processing(enemy.clone())
+	///         .spawn();
+	///
+	///     // Suspends the patrol, until processing is enabled again.
+	///     enemy.set_process(false);
+	/// }
+	/// ```
+	pub fn mirror_owner_processing(self, owner: Gd<impl Inherits<Node>>) -> Self {
+		Self {
+			mirrored_node: Some(owner.upcast()),
+			..self
+		}
+	}
+
+	/// Tags the coroutine with `name`, so that it can be controlled along with every other coroutine tagged the same,
+	/// regardless of their owners, see [kill_group], [pause_group] and [resume_group].
+	///
+	/// The coroutine's node joins the Godot group `name`, which also makes it reachable through 
+	/// [SceneTree::call_group](godot::classes::SceneTree::call_group) and similar. Calling this again replaces the previous tag.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_group(enemy: Gd<Node2D>) {
+	///     enemy.coroutine(
+	///         #[coroutine] || {
+	///             for _ in 0..10 {
+	///                 yield seconds(1.0);
+	///                 // Patrol...
+	///             }
+	///         })
+	///         .group("ai")
+	///         .spawn();
+	///
+	///     // Later, e.g. when a cutscene starts:
+	///     pause_group("ai");
+	/// }
+	/// ```
+	pub fn group(self, name: impl Into<StringName>) -> Self {
+		Self {
+			group: Some(name.into()),
+			..self
+		}
+	}
+
+	/// Sets the [name](Node::set_name) of the coroutine's node, making it easier to tell coroutines apart in the remote scene tree.
+	///
+	/// By default, nodes are named by Godot, e.g. `@SpireCoroutine@1234`.
+	/// Nodes reused by a [CoroutinePool] get a generated name in the same format, they don't keep the name of their previous coroutine.
+	/// Like with any other node, if the owner already has a child with the same name, Godot makes the name unique when spawning.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_named(door: Gd<Node2D>) {
+	///     door.coroutine(
+	///         #[coroutine] || {
+	///             yield seconds(1.0);
+	///             // Open door...
+	///         })
+	///         .named("OpenDoor")
+	///         .spawn();
+	/// }
+	/// ```
+	pub fn named(self, name: impl Into<GString>) -> Self {
+		Self {
+			name: Some(name.into()),
+			..self
+		}
+	}
+
+	/// Adds `f` to the list of closures that will be invoked when the coroutine finishes.
+	///
+	/// The return value of the coroutine(`T`) will be passed to `f`.
+	/// 
+	/// [finished](SIGNAL_FINISHED) is only emitted if the coroutine finishes normally.
+	/// 
+	/// Some cases where the coroutine's end is considered "abnormal":
+	/// - The parent node of the coroutine was deleted (freed)
+	/// - The coroutine's main closure panics
+	/// - The coroutine ends with [force_run_to_completion](SpireCoroutine::force_run_to_completion)
+	/// - The coroutine ends with [kill](SpireCoroutine::kill), unless it was spawned with [emit_on_kill](Self::emit_on_kill)
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_finish(node: Gd<Node2D>) {
+	///     node.coroutine(
+	///         #[coroutine] || { 
+	///             yield frames(2);
+	///             return 5;
+	///         })
+	///         .on_finish(|res| println!("Coroutine finished, result: {res}"))
+	///         .spawn();
+	/// }
+	/// ```
+	pub fn on_finish(
+		self,
+		f: impl 'static + FnOnce(R),
+	) -> Self
+		where
+			R: FromGodot,
+	{
+		let wrapper =
+			move |var: Variant| {
+				match var.try_to::<R>() {
+					Ok(r) => { f(r); }
+					Err(err) => {
+						godot_error!("{err}");
+					}
+				}
+			};
+
+		let mut calls_on_finish = self.calls_on_finish;
+		calls_on_finish.push(OnFinishCall::Closure(Box::new(wrapper)));
+
+		Self {
+			calls_on_finish,
+			result_check: Some(ResultCheck::of::<R>()),
+			..self
+		}
+	}
+
+	/// Adds `f` to the list of closures that will be invoked whenever the coroutine finishes.
+	///
+	/// Unlike [on_finish](Self::on_finish), `f` is not consumed when invoked: it stays registered for as long as the coroutine
+	/// exists, so coroutines that finish more than once report the result of each completion.
+	/// Otherwise, it behaves exactly like [on_finish](Self::on_finish), including when it is not invoked.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_on_each_finish(node: Gd<Node2D>) {
+	///     let mut completions = 0;
+	///
+	///     node.coroutine(
+	///         #[coroutine] || {
+	///             yield seconds(1.0);
+	///             5
+	///         })
+	///         .on_each_finish(move |res| {
+	///             completions += 1;
+	///             println!("Completion #{completions}, result: {res}");
+	///         })
+	///         .spawn();
+	/// }
+	/// ```
+	pub fn on_each_finish(
+		self,
+		mut f: impl 'static + FnMut(R),
+	) -> Self
+		where
+			R: FromGodot,
+	{
+		let wrapper =
+			move |var: Variant| {
+				match var.try_to::<R>() {
+					Ok(r) => { f(r); }
+					Err(err) => {
+						godot_error!("{err}");
+					}
+				}
+			};
+
+		let mut calls_on_finish = self.calls_on_finish;
+		calls_on_finish.push(OnFinishCall::Repeating(Box::new(wrapper)));
+
+		Self {
+			calls_on_finish,
+			result_check: Some(ResultCheck::of::<R>()),
+			..self
+		}
+	}
+
+	/// Spawns the coroutine returned by `f` once this coroutine finishes, like [on_finish](Self::on_finish)
+	/// but for follow-ups that need to span multiple frames.
+	///
+	/// The continuation is spawned with default settings, as a child of this coroutine's owner.
+	/// It is not spawned if the owner was freed in the meantime.
+	///
+	/// This coroutine still emits [finished](SIGNAL_FINISHED) with its own result,
+	/// the continuation is a separate [SpireCoroutine].
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// #![feature(stmt_expr_attributes)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_and_then_spawn(node: Gd<Node2D>) {
+	///     node.coroutine(
+	///         #[coroutine] || {
+	///             yield seconds(2.0);
+	///             String::from("Fading out")
+	///         })
+	///         .and_then_spawn(|message| 
+	///             #[coroutine] move || {
+	///                 godot_print!("{message}");
+	///                 yield seconds(1.0);
+	///                 godot_print!("Faded out!");
+	///             })
+	///         .spawn();
+	/// }
+	/// ```
+	pub fn and_then_spawn<C, R2>(
+		self,
+		f: impl 'static + FnOnce(R) -> C,
+	) -> Self
+		where
+			R: FromGodot,
+			C: 'static + PollRoutine<Return = R2>,
+			R2: 'static + ToGodot,
+	{
+		let owner = self.owner.clone();
+
+		self.on_finish(move |result| {
+			if owner.is_instance_valid() {
+				owner.start_coroutine(f(result));
+			} else {
+				godot_warn!("Coroutine's owner was freed before it finished, its continuation won't be spawned.");
+			}
+		})
+	}
+
+	/// Adds `f` to the list of closures that will be invoked on every poll, before the coroutine's closure is resumed.
+	///
+	/// `f` receives the poll's delta time. It is not invoked while the coroutine is paused.
+	///
+	/// Useful for attaching instrumentation or UI updates to coroutines without editing their body.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::classes::Label;
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_on_each_frame(node: Gd<Node2D>, mut label: Gd<Label>) {
+	///     let mut elapsed = 0.0;
+	///
+	///     node.coroutine(
+	///         #[coroutine] || {
+	///             yield seconds(5.0);
+	///         })
+	///         .on_each_frame(move |delta| {
+	///             elapsed += delta;
+	///             label.set_text(&format!("Elapsed: {elapsed:.1}s"));
+	///         })
+	///         .spawn();
+	/// }
+	/// ```
+	pub fn on_each_frame(self, f: impl 'static + FnMut(f64)) -> Self {
+		let mut calls_on_each_frame = self.calls_on_each_frame;
+		calls_on_each_frame.push(Box::new(f));
+
+		Self {
+			calls_on_each_frame,
+			..self
+		}
+	}
+
+	/// Adds `f` to the list of closures that will be invoked if the coroutine is [killed](SpireCoroutine::kill) before finishing.
+	///
+	/// This includes being aborted by a yield (see [WaitOutcome::Abort]) and panicking.
+	/// Not invoked if the coroutine finishes normally.
+	pub fn on_cancel(self, f: impl 'static + FnOnce()) -> Self {
+		let mut calls_on_cancel = self.calls_on_cancel;
+		calls_on_cancel.push(Box::new(f));
+
+		Self {
+			calls_on_cancel,
+			..self
+		}
+	}
+
+	/// Adds `f` to the list of closures that will be invoked exactly once when the coroutine ends, whichever way it ends:
+	/// finishing, being [killed](SpireCoroutine::kill) (including aborting and panicking), or being freed along with its owner.
+	///
+	/// Invoked after the [on_finish](Self::on_finish) or [on_cancel](Self::on_cancel) callbacks, which makes it the place
+	/// for teardown that must always happen, like releasing a resource acquired by the coroutine.
+	///
+	/// Moving the coroutine out of the tree (e.g. with [reparent_to]) does not invoke `f`, only ending it does.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::classes::Control;
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_on_exit(node: Gd<Node2D>, mut input_blocker: Gd<Control>) {
+	///     input_blocker.show();
+	///     let mut blocker = input_blocker.clone();
+	///
+	///     node.coroutine(
+	///         #[coroutine] || {
+	///             yield seconds(3.0);
+	///         })
+	///         .on_exit(move || blocker.hide())
+	///         .spawn();
+	/// }
+	/// ```
+	pub fn on_exit(self, f: impl 'static + FnOnce()) -> Self {
+		let mut calls_on_exit = self.calls_on_exit;
+		calls_on_exit.push(Box::new(f));
+
+		Self {
+			calls_on_exit,
+			..self
+		}
+	}
+
+	/// When the coroutine is [killed](SpireCoroutine::kill), it finishes with `default` instead of ending silently:
+	/// the [on_finish](Self::on_finish) callbacks are invoked and [finished](SIGNAL_FINISHED) is emitted, 
+	/// after the [on_cancel](Self::on_cancel) callbacks.
+	///
+	/// Lets awaiters of the coroutine resume with a sentinel value, instead of waiting forever.
+	/// `default` should be convertible to `R`, otherwise typed callbacks like [on_finish](Self::on_finish) log an error.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_emit_on_kill(node: Gd<Node>) -> Gd<SpireCoroutine> {
+	///     node.coroutine(
+	///         #[coroutine] || {
+	///             yield seconds(10.0);
+	///             100
+	///         })
+	///         .emit_on_kill((-1).to_variant())
+	///         .on_finish(|score| godot_print!("Score: {score}"))
+	///         .spawn()
+	/// }
+	/// ```
+	pub fn emit_on_kill(self, default: Variant) -> Self {
+		Self {
+			kill_result: Some(default),
+			..self
+		}
+	}
+
+	/// If the coroutine's closure panics, replaces it with a new one created by `make` instead of killing the coroutine.
+	///
+	/// The new closure starts running on the coroutine's next poll, and a warning with the panic's reason is logged.
+	/// The panicked closure is leaked, as usual.
+	///
+	/// After `max_restarts` restarts, the next panic kills the coroutine, preventing endless panic loops.
+	///
+	/// `make` is also used by [restart](SpireCoroutine::restart), as if given to [restartable](Self::restartable):
+	/// both share a single factory, whichever is called last replaces it.
+	///
+	/// Useful for long-lived background coroutines, where a transient panic shouldn't stop the service for good.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn poll_server() {}
+	///
+	/// fn showcase_restart_on_panic(node: Gd<Node>) {
+	///     let service = || {
+	///         #[coroutine] || {
+	///             for _ in 0..10_000 {
+	///                 poll_server();
+	///                 yield seconds(1.0);
+	///             }
+	///         }
+	///     };
+	///
+	///     node.coroutine(service())
+	///         .restart_on_panic(3, service)
+	///         .spawn();
+	/// }
+	/// ```
+	pub fn restart_on_panic<C>(self, max_restarts: u32, make: impl 'static + Fn() -> C) -> Self
+		where
+			C: 'static + PollRoutine<Return = R>,
+	{
+		Self {
+			panic_restart: Some(PanicRestart { restarts_left: max_restarts }),
+			..self.restartable(make)
+		}
+	}
+
+	/// Allows [restarting](SpireCoroutine::restart) the coroutine, which replaces its closure with a new one created by `make`.
+	///
+	/// `make` should build the same closure the coroutine was created with, which makes it easy to re-run
+	/// looping animations or to retry tasks from the beginning.
+	///
+	/// The factory is shared with [restart_on_panic](Self::restart_on_panic), whichever is called last replaces it.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_restartable(node: Gd<Node2D>) -> Gd<SpireCoroutine> {
+	///     let target = node.clone();
+	///     let shake = move || {
+	///         let mut target = target.clone();
+	///         #[coroutine] move || {
+	///             for offset in [4.0, -4.0, 2.0, -2.0] {
+	///                 target.set_position(Vector2::new(offset, 0.0));
+	///                 yield frames(1);
+	///             }
+	///         }
+	///     };
+	///
+	///     node.coroutine(shake())
+	///         .restartable(shake)
+	///         .spawn()
+	/// }
+	///
+	/// fn on_hit(shake: &mut Gd<SpireCoroutine>) {
+	///     shake.bind_mut().restart();
+	/// }
+	/// ```
+	pub fn restartable<C>(self, make: impl 'static + Fn() -> C) -> Self
+		where
+			C: 'static + PollRoutine<Return = R>,
+	{
+		Self {
+			restart_factory: Some(Box::new(move || into_routine(make()))),
+			..self
+		}
+	}
+
+	/// See [on_finish](SpireCoroutine::on_finish)
+	/// 
+	/// This variant takes a [Callable] instead of a closure.
+	pub fn on_finish_callable(
+		self,
+		callable: Callable,
+	) -> Self
+		where
+			R: FromGodot,
+	{
+		let mut calls_on_finish = self.calls_on_finish;
+		calls_on_finish.push(OnFinishCall::Callable(callable));
+
+		Self {
+			calls_on_finish,
+			result_check: Some(ResultCheck::of::<R>()),
+			..self
+		}
+	}
+
+	/// Swaps the coroutine's closure with `f`, keeping every other setting (owner, poll mode, process mode, auto-start, pool, etc.).
+	///
+	/// If `keep_calls_on_finish` is false, the callbacks registered with [on_finish](Self::on_finish) and similar are discarded.
+	/// Otherwise they are kept, and will be invoked with `f`'s result,
+	/// which means typed callbacks will log errors if `R2` can't be converted into the original return type.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_rebind(node: Gd<Node2D>) {
+	///     let template =
+	///         node.coroutine(
+	///             #[coroutine] || {
+	///                 yield frames(1);
+	///             })
+	///             .poll_mode(PollMode::Physics)
+	///             .auto_start(false);
+	///
+	///     let coroutine: Gd<SpireCoroutine> =
+	///         template.rebind(
+	///             #[coroutine] || {
+	///                 yield seconds(1.0);
+	///                 5
+	///             }, false)
+	///             .spawn();
+	/// }
+	/// ```
+	pub fn rebind<R2>(
+		self,
+		f: impl 'static + PollRoutine<Return = R2>,
+		keep_calls_on_finish: bool,
+	) -> CoroutineBuilder<R2>
+		where
+			R2: 'static + ToGodot,
+	{
+		let (calls_on_finish, result_check, result_map, kill_result) =
+			if keep_calls_on_finish {
+				(self.calls_on_finish, self.result_check, self.result_map, self.kill_result)
+			} else {
+				(Vec::new(), None, None, None)
+			};
+
+		CoroutineBuilder {
+			poll_mode: self.poll_mode,
+			process_mode: self.process_mode,
+			process_priority: self.process_priority,
+			physics_priority: self.physics_priority,
+			auto_start: self.auto_start,
+			start_mode: self.start_mode,
+			start_gate: self.start_gate,
+			calls_on_finish,
+			calls_on_each_frame: self.calls_on_each_frame,
+			calls_on_cancel: self.calls_on_cancel,
+			calls_on_exit: self.calls_on_exit,
+			result_check,
+			result_map,
+			kill_result,
+			emit_waiting: self.emit_waiting,
+			time_scale: self.time_scale,
+			start_delay: self.start_delay,
+			max_steps_per_frame: self.max_steps_per_frame,
+			stuck_threshold: self.stuck_threshold,
+			pool: self.pool,
+			group: self.group,
+			name: self.name,
+			keep_alive: self.keep_alive,
+			yield_records: self.yield_records,
+			mirrored_node: self.mirrored_node,
+			metadata: self.metadata,
+			..CoroutineBuilder::new_coroutine(self.owner, f)
+		}
+	}
+
+	/// Same as [spawn](Self::spawn), but the coroutine is added as a child of `parent` instead of the node the builder was created from.
+	///
+	/// Useful for tying the coroutine's lifetime to a different node, e.g. a central effects root that outlives the caller.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_spawn_on(enemy: Gd<Node2D>, effects_root: Gd<Node>) {
+	///     enemy.coroutine(
+	///             #[coroutine] || {
+	///                 yield seconds(5.0);
+	///             })
+	///         .spawn_on(effects_root);
+	/// }
+	/// ```
+	pub fn spawn_on(self, parent: Gd<impl Inherits<Node>>) -> Gd<SpireCoroutine> {
+		Self {
+			owner: parent.upcast(),
+			..self
+		}.spawn()
+	}
+
+	/// Same as [spawn](Self::spawn), but the coroutine starts paused, as if spawned with [auto_start(false)](Self::auto_start).
+	///
+	/// Call [start](SpireCoroutine::start) on the returned handle to start it.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_spawn_paused(node: Gd<Node2D>) {
+	///     let mut intro =
+	///         node.coroutine(
+	///                 #[coroutine] || {
+	///                     yield seconds(2.0);
+	///                 })
+	///             .spawn_paused();
+	///
+	///     // Later, once the scene is ready:
+	///     intro.bind_mut().start();
+	/// }
+	/// ```
+	pub fn spawn_paused(self) -> Gd<SpireCoroutine> {
+		Self {
+			auto_start: false,
+			..self
+		}.spawn()
+	}
+
+	/// Completes the builder, spawning the coroutine's executor.
+	///
+	/// The executor is the type [SpireCoroutine], a node that will be added as a child of `owner`.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_spawn(node: Gd<Node2D>) {
+	///     let builder = 
+	///         node.coroutine(
+	///             #[coroutine] || {
+	///                 yield frames(5);
+	///                 yield seconds(5.0);
+	///             });
+	///
+	///     let coroutine: Gd<SpireCoroutine> = builder.spawn();
+	/// }
+	/// ```
+	pub fn spawn(self) -> Gd<SpireCoroutine> {
+		let mut coroutine =
+			self.pool
+				.as_ref()
+				.and_then(CoroutinePool::take_idle)
+				.unwrap_or_else(|| Gd::from_init_fn(SpireCoroutine::blank));
+
+		{
+			let mut bind = coroutine.bind_mut();
+			bind.driver = CoroutineDriver::from_routine(delay_routine(self.f, self.start_delay));
+			bind.poll_mode = self.poll_mode;
+			bind.paused = !self.auto_start || self.start_gate.is_some();
+			bind.is_async = self.is_async;
+			bind.start_gate = if self.auto_start { self.start_gate } else { None };
+			bind.calls_on_finish = self.calls_on_finish;
+			bind.calls_on_each_frame = self.calls_on_each_frame;
+			bind.calls_on_cancel = self.calls_on_cancel;
+			bind.calls_on_exit = self.calls_on_exit;
+			bind.panic_restart = self.panic_restart;
+			bind.restart_factory =
+				match self.restart_factory {
+					Some(make) if self.start_delay > 0.0 => {
+						let start_delay = self.start_delay;
+						Some(Box::new(move || delay_routine(make(), start_delay)))
+					}
+					restart_factory => restart_factory,
+				};
+			bind.kill_result = self.kill_result;
+			bind.result_check = self.result_check;
+			bind.result_map = self.result_map;
+			bind.emit_waiting = self.emit_waiting;
+			bind.time_scale = self.time_scale;
+			bind.max_steps_per_frame = self.max_steps_per_frame;
+			bind.stuck_threshold = self.stuck_threshold;
+			bind.pool = self.pool;
+			bind.group = self.group.clone();
+			bind.keep_alive = self.keep_alive;
+			bind.yield_records = self.yield_records;
+			bind.mirrored_node = self.mirrored_node;
+			bind.ended = false;
+			bind.spawn_sequence = SPAWN_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+		}
+
+		crate::stats::on_spawn();
+
+		coroutine.set_process_priority(
+			self.process_priority.unwrap_or_else(|| DEFAULT_PROCESS_PRIORITY.load(Ordering::Relaxed)));
+		coroutine.set_physics_process_priority(
+			self.physics_priority.unwrap_or_else(|| DEFAULT_PHYSICS_PRIORITY.load(Ordering::Relaxed)));
+
+		coroutine.set_process_mode(self.process_mode);
+
+		if let Some(name) = &self.name {
+			coroutine.set_name(name);
+		}
+
+		if let Some(group) = &self.group {
+			coroutine.add_to_group(group);
+		}
+
+		for (key, value) in self.metadata.iter_shared() {
+			coroutine.set_meta(&StringName::from(&key.stringify()), &value);
+		}
+
+		let mut owner = self.owner;
+		owner.add_child(&coroutine);
+
+		if self.start_mode == CoroutineStartMode::Immediate && !coroutine.bind().is_paused() {
+			coroutine.bind_mut().step(0.0);
+		}
+
+		coroutine
+	}
+}
+
+#[cfg(feature = "async")]
+mod async_bridge {
+	use std::future::Future;
+	use std::pin::Pin;
+	use std::task::{Context, Poll, Waker};
+	use godot::prelude::*;
+	use smol::Task;
+
+	use crate::prelude::{frames, Step};
+
+	/// Polls `f` once on the calling thread, without blocking nor registering a waker.
+	pub fn poll_now<F: Future + Unpin>(f: &mut F) -> Poll<F::Output> {
+		Pin::new(f).poll(&mut Context::from_waker(Waker::noop()))
+	}
+
+	/// Retrieves the output of a task that already [finished](Task::is_finished), without blocking.
+	///
+	/// The task is polled instead of awaited with `block_on`, so that requesting the result of an unfinished task
+	/// can never stall the main thread.
+	///
+	/// Returns `None` (after logging an error) if the task panicked.
+	pub fn take_result<T>(mut task: Task<std::thread::Result<T>>) -> Option<T> {
+		debug_assert!(task.is_finished(), "Async task's result was requested before the task finished.");
+
+		match poll_now(&mut task) {
+			Poll::Ready(result) => unwrap_or_log(result),
+			Poll::Pending => {
+				godot_error!("Async task was not finished when its result was requested.");
+				None
+			}
+		}
+	}
+
+	/// Finishes with the output of `task` (`nil` if it panicked) once it's finished, converted by `into_variant`.
+	///
+	/// `task` is taken once finished, so the returned step must be the last one.
+	pub fn wait_for_task<T>(
+		task: &mut Option<Task<std::thread::Result<T>>>,
+		into_variant: impl FnOnce(T) -> Variant,
+	) -> Step<Variant> {
+		match task.take_if(|task| task.is_finished()) {
+			Some(task) => Step::Done(take_result(task).map_or_else(Variant::nil, into_variant)),
+			None => Step::Wait(frames(1)),
+		}
+	}
+
+	pub fn unwrap_or_log<T>(result: std::thread::Result<T>) -> Option<T> {
+		match result {
+			Ok(output) => Some(output),
+			Err(err) => {
+				godot_error!("Async task panicked, the coroutine will finish with `nil`.\n\
+							  Panic Reason: \"{}\"", crate::panic_reason(&err));
+				None
+			}
+		}
 	}
 }
 
@@ -1,11 +1,17 @@
-use std::ops::{Coroutine, CoroutineState};
+use std::cell::{Cell, RefCell};
 use std::panic::AssertUnwindSafe;
-use std::pin::Pin;
+use std::rc::Rc;
 
+use godot::classes::notify::NodeNotification;
 use godot::obj::WithBaseField;
 use godot::prelude::*;
 
-use crate::OnFinishCall;
+use crate::{OnFinishCall, PanicRestart, ResultCheck};
+use crate::driver::{CoroutineDriver, Routine, WaitPoll};
+use crate::pool::CoroutinePool;
+use crate::routine::RoutineState;
+use crate::stats;
+use crate::yield_record::{YieldKind, YieldRecord};
 use crate::yielding::SpireYield;
 
 /// A Godot class responsible for managing a coroutine.
@@ -14,19 +20,113 @@ use crate::yielding::SpireYield;
 /// - [crate::prelude::CoroutineBuilder]
 /// - [node.start_coroutine](crate::prelude::StartCoroutine::start_coroutine)
 /// - [node.start_async_task](crate::prelude::StartAsyncTask::start_async_task) (requires feature "async")
+///
+/// # Polling order
+///
+/// Within a frame, coroutines are polled in the order Godot processes nodes: by [process priority](Node::set_process_priority),
+/// then by their position in the scene tree. Since coroutines are appended as the last child of their owner,
+/// coroutines with the same owner and priority are polled in spawn order,
+/// which means their `finished` signals and [on_finish](crate::prelude::CoroutineBuilder::on_finish) callbacks
+/// also fire in spawn order when they finish on the same frame.
+///
+/// For coroutines with different owners, [get_spawn_sequence](Self::get_spawn_sequence) can be used to sort completions.
 #[derive(GodotClass)]
 #[class(no_init, base = Node)]
 pub struct SpireCoroutine {
 	pub(crate) base: Base<Node>,
-	pub(crate) coroutine: Box<dyn Unpin + Coroutine<(), Yield = SpireYield, Return = Variant>>,
+	/// Holds the closure and handles its yields.
+	pub(crate) driver: CoroutineDriver,
 	pub(crate) poll_mode: PollMode,
-	pub(crate) last_yield: Option<SpireYield>,
 	pub(crate) paused: bool,
+	pub(crate) is_async: bool,
 	pub(crate) calls_on_finish: Vec<OnFinishCall>,
+	/// Invoked with the delta time at the start of every poll.
+	pub(crate) calls_on_each_frame: Vec<Box<dyn FnMut(f64)>>,
+	/// Invoked when the coroutine is killed (including by [WaitOutcome::Abort]) before finishing.
+	pub(crate) calls_on_cancel: Vec<Box<dyn FnOnce()>>,
+	/// Invoked once the coroutine ends, however it ends, see [CoroutineBuilder::on_exit](crate::prelude::CoroutineBuilder::on_exit).
+	pub(crate) calls_on_exit: Vec<Box<dyn FnOnce()>>,
+	pub(crate) panic_restart: Option<PanicRestart>,
+	/// Rebuilds the closure on [restart](Self::restart) and after panics, see [CoroutineBuilder::restartable](crate::prelude::CoroutineBuilder::restartable)
+	/// and [CoroutineBuilder::restart_on_panic](crate::prelude::CoroutineBuilder::restart_on_panic).
+	pub(crate) restart_factory: Option<Box<dyn Fn() -> Routine>>,
+	/// If set, [kill](Self::kill) finishes the coroutine with this result instead of ending it silently.
+	pub(crate) kill_result: Option<Variant>,
+	/// While set, the coroutine stays paused until this returns true, see [CoroutineBuilder::start_when](crate::prelude::CoroutineBuilder::start_when).
+	pub(crate) start_gate: Option<Box<dyn FnMut() -> bool>>,
+	pub(crate) result_check: Option<ResultCheck>,
+	pub(crate) result_map: Option<Box<dyn Fn(Variant) -> Variant>>,
+	pub(crate) emit_waiting: bool,
+	/// Multiplies the delta time the coroutine receives from [process](INode::process)/[physics_process](INode::physics_process).
+	pub(crate) time_scale: f64,
+	pub(crate) max_steps_per_frame: u32,
+	pub(crate) steps_left: u32,
+	/// True while the coroutine is being polled, during which de-spawning is deferred.
+	pub(crate) polling: bool,
+	/// True once the coroutine was killed or finished, even if it hasn't de-spawned yet.
+	pub(crate) ended: bool,
+	/// How many consecutive polls the coroutine spent waiting on its current [Dyn](SpireYield::Dyn)/[Inline](SpireYield::Inline) yield.
+	pub(crate) dyn_polls: u64,
+	/// If set, a warning is logged once [dyn_polls](Self::dyn_polls) reaches this amount.
+	pub(crate) stuck_threshold: Option<u64>,
+	/// If set, the node returns to this pool instead of being freed.
+	pub(crate) pool: Option<CoroutinePool>,
+	/// The Godot group the node joined when spawning, see [CoroutineBuilder::group](crate::prelude::CoroutineBuilder::group).
+	pub(crate) group: Option<StringName>,
+	/// If true, the node isn't freed once the coroutine ends, see [CoroutineBuilder::keep_alive](crate::prelude::CoroutineBuilder::keep_alive).
+	pub(crate) keep_alive: bool,
+	/// The coroutine's result, only stored if [keep_alive](Self::keep_alive) is set.
+	pub(crate) result: Option<Variant>,
+	/// If set, every processed yield is appended to it, see [CoroutineBuilder::record_yields](crate::prelude::CoroutineBuilder::record_yields).
+	pub(crate) yield_records: Option<Rc<RefCell<Vec<YieldRecord>>>>,
+	/// While this node isn't processing, the coroutine isn't polled,
+	/// see [CoroutineBuilder::mirror_owner_processing](crate::prelude::CoroutineBuilder::mirror_owner_processing).
+	pub(crate) mirrored_node: Option<Gd<Node>>,
+	pub(crate) finish_reason: FinishReason,
+	/// Incremented whenever the node is recycled by a [CoroutinePool].
+	pub(crate) generation: u64,
+	/// Assigned on spawn, see [get_spawn_sequence](Self::get_spawn_sequence).
+	pub(crate) spawn_sequence: u64,
+}
+
+thread_local! {
+	static CURRENT: RefCell<Option<Gd<SpireCoroutine>>> = const { RefCell::new(None) };
+	static FORCING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` with [SpireCoroutine::is_forcing] returning true.
+fn forcing<T>(f: impl FnOnce() -> T) -> T {
+	let was_forcing = FORCING.replace(true);
+	let result = f();
+	FORCING.set(was_forcing);
+	result
+}
+
+/// How many polls a coroutine may spend on the same custom yield before [SpireCoroutine::is_possibly_stuck] returns true,
+/// unless overridden with [CoroutineBuilder::stuck_threshold](crate::prelude::CoroutineBuilder::stuck_threshold).
+pub const STUCK_THRESHOLD_DEFAULT: u64 = 600;
+
+/// How a coroutine ended, see [SpireCoroutine::get_finish_reason].
+///
+/// Exposed to GDScript as an integer, in declaration order (`Running` is 0).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, GodotConvert, Var, Export)]
+#[godot(via = i64)]
+pub enum FinishReason {
+	/// The coroutine hasn't ended yet.
+	Running,
+	/// The coroutine's closure returned, or [finish_with](SpireCoroutine::finish_with) was called.
+	Completed,
+	/// The coroutine was [killed](SpireCoroutine::kill), including by a yield [aborting](WaitOutcome::Abort) it.
+	Killed,
+	/// The coroutine's closure panicked (and wasn't [restarted](crate::prelude::CoroutineBuilder::restart_on_panic)).
+	Panicked,
+	/// The coroutine's node was freed before it ended, e.g. along with its owner.
+	OwnerFreed,
 }
 
 /// Defines whether the coroutine polls on process or physics frames. 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, GodotConvert, Var, Export)]
+#[godot(via = i64)]
 pub enum PollMode {
 	Process,
 	Physics,
@@ -34,15 +134,33 @@ pub enum PollMode {
 
 #[godot_api]
 impl INode for SpireCoroutine {
+	fn on_notification(&mut self, what: NodeNotification) {
+		// Freed without ending, e.g. along with its owner.
+		if what == NodeNotification::PREDELETE && !self.ended {
+			self.ended = true;
+			self.finish_reason = FinishReason::OwnerFreed;
+			stats::on_end();
+			self.run_exit_calls();
+		}
+	}
+
 	fn process(&mut self, delta: f64) {
-		if !self.paused && self.poll_mode == PollMode::Process {
-			self.run(delta);
+		if self.poll_mode == PollMode::Process {
+			self.check_start_gate();
+
+			if !self.paused && self.mirrored_node_is_processing() {
+				self.run(delta * self.time_scale);
+			}
 		}
 	}
 
 	fn physics_process(&mut self, delta: f64) {
-		if !self.paused && self.poll_mode == PollMode::Physics {
-			self.run(delta);
+		if self.poll_mode == PollMode::Physics {
+			self.check_start_gate();
+
+			if !self.paused && self.mirrored_node_is_processing() {
+				self.run(delta * self.time_scale);
+			}
 		}
 	}
 }
@@ -66,7 +184,7 @@ impl INode for SpireCoroutine {
 ///                 return "Hello, I'm 2 seconds late!";
 ///             });
 ///      
-///     coroutine.connect(SIGNAL_FINISHED.into(), Callable::from_fn("print_result", 
+///     coroutine.connect(SIGNAL_FINISHED, &Callable::from_local_fn("print_result", 
 ///         |args| {
 ///             let result = args.first().and_then(|var| var.try_to::<String>().ok()).unwrap();
 ///             assert_eq!(result.as_str(), "Hello, I'm 2 seconds late!");
@@ -74,13 +192,108 @@ impl INode for SpireCoroutine {
 ///         }));
 /// }
 /// ```
+///
+/// [SpireCoroutine::connect_finished] does the same connection and conversion in a single call.
+///
+/// The signal is emitted with a single argument, so GDScript can `await` it directly to get the result:
+///
+/// ```gdscript
+/// var coroutine: SpireCoroutine = rust_node.start_something()
+/// var result = await coroutine.finished
+/// ```
+///
+/// Coroutines that were [killed](SpireCoroutine::kill) don't emit this signal, so awaiting them never resumes,
+/// unless they were spawned with [emit_on_kill](crate::prelude::CoroutineBuilder::emit_on_kill),
+/// in which case the signal is emitted with the configured default as the argument.
 pub const SIGNAL_FINISHED: &str = "finished";
 
+/// The name of the waiting signal.
+///
+/// Emitted on every poll that doesn't finish the coroutine, with the total seconds the coroutine has been running as the argument.
+///
+/// Only emitted if the coroutine was built with [emit_waiting](crate::prelude::CoroutineBuilder::emit_waiting),
+/// this is mostly useful for async tasks, which otherwise give no feedback until they finish.
+pub const SIGNAL_WAITING: &str = "waiting";
+
+/// The name of the value signal.
+///
+/// Emitted whenever the coroutine's closure yields [yield_value](crate::prelude::yield_value), with the yielded value as the argument.
+///
+/// Meant for observing the progress of coroutines that compute something incrementally, 
+/// e.g. connecting a progress bar to a coroutine that generates a level.
+pub const SIGNAL_VALUE: &str = "value";
+
 #[godot_api]
 impl SpireCoroutine {
 	#[signal]
 	fn finished(result: Variant) {}
 
+	#[signal]
+	fn waiting(elapsed: f64) {}
+
+	#[signal]
+	fn value(value: Variant) {}
+
+	/// Returns the handle of the coroutine whose closure is currently executing, if any.
+	///
+	/// This allows a coroutine to reach its own handle from inside its closure,
+	/// for example to rename itself, pass itself to another system, or pause itself.
+	///
+	/// While its closure executes, the coroutine can be bound (through [bind](Gd::bind)/[bind_mut](Gd::bind_mut)) re-entrantly.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use gdext_coroutines::prelude::*;
+	/// use godot::prelude::*;
+	///
+	/// fn showcase_current(node: Gd<Node>) {
+	///     node.start_coroutine(
+	///         #[coroutine] || {
+	///             let mut me = SpireCoroutine::current().unwrap();
+	///             me.set_name("Self-named coroutine");
+	///             yield frames(1);
+	///             godot_print!("Pausing myself!");
+	///             me.bind_mut().pause();
+	///             yield frames(1);
+	///             godot_print!("Someone resumed me!");
+	///         });
+	/// }
+	/// ```
+	pub fn current() -> Option<Gd<SpireCoroutine>> {
+		CURRENT.with_borrow(|current| current.clone())
+	}
+
+	/// Returns true while a coroutine is being force-run, through [force_run_to_completion](Self::force_run_to_completion)
+	/// or [advance_time](Self::advance_time), false during regular polls.
+	///
+	/// Well-behaved coroutines can use this to collapse visual-only waits (e.g. animations) when being force-completed.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use gdext_coroutines::prelude::*;
+	/// use godot::prelude::*;
+	///
+	/// fn showcase_is_forcing(node: Gd<Node>, mut sprite: Gd<Node2D>) {
+	///     node.start_coroutine(
+	///         #[coroutine] move || {
+	///             for step in 1..=10 {
+	///                 sprite.set_modulate(Color::from_rgba(1.0, 1.0, 1.0, step as f32 / 10.0));
+	///
+	///                 if !SpireCoroutine::is_forcing() {
+	///                     yield frames(1);
+	///                 }
+	///             }
+	///         });
+	/// }
+	/// ```
+	pub fn is_forcing() -> bool {
+		FORCING.get()
+	}
+
 	#[func]
 	pub fn is_paused(&self) -> bool {
 		self.paused
@@ -91,12 +304,293 @@ impl SpireCoroutine {
 	/// - The coroutine is not finished
 	#[func]
 	pub fn is_running(&self) -> bool {
-		!self.paused && !self.base().is_queued_for_deletion()
+		!self.paused && !self.is_finished()
 	}
 
 	#[func]
 	pub fn is_finished(&self) -> bool {
-		self.base().is_queued_for_deletion()
+		self.ended || self.base().is_queued_for_deletion()
+	}
+
+	/// Best-effort heuristic for detecting coroutines that will never resume.
+	///
+	/// Returns true if the coroutine has been waiting on the same custom yield
+	/// (e.g. [wait_until], [wait_while], [KeepWaiting] implementations) for at least [STUCK_THRESHOLD_DEFAULT] polls,
+	/// or the threshold set with [CoroutineBuilder::stuck_threshold].
+	///
+	/// A long wait isn't necessarily a bug, this is meant for diagnostics during development.
+	#[func]
+	pub fn is_possibly_stuck(&self) -> bool {
+		self.dyn_polls >= self.stuck_threshold.unwrap_or(STUCK_THRESHOLD_DEFAULT)
+	}
+
+	/// Returns a number that increases with every coroutine spawned, which can be used to order coroutines by spawn time.
+	///
+	/// Pooled coroutines get a new sequence number whenever they are spawned again.
+	#[func]
+	pub fn get_spawn_sequence(&self) -> u64 {
+		self.spawn_sequence
+	}
+
+	/// Returns the tag the coroutine was spawned with, see [CoroutineBuilder::group](crate::prelude::CoroutineBuilder::group).
+	///
+	/// Returns an empty name if the coroutine isn't tagged.
+	#[func]
+	pub fn get_group(&self) -> StringName {
+		self.group.clone().unwrap_or_default()
+	}
+
+	/// Returns how many times this node was recycled by a [CoroutinePool].
+	///
+	/// Handles to pooled coroutines may end up pointing to a different coroutine,
+	/// comparing generations tells whether the node was reused in the meantime.
+	#[func]
+	pub fn get_generation(&self) -> u64 {
+		self.generation
+	}
+
+	/// Returns the sum of the delta times of every poll the coroutine went through.
+	///
+	/// Time does not pass while the coroutine is paused.
+	#[func]
+	pub fn get_elapsed_seconds(&self) -> f64 {
+		self.driver.elapsed_seconds
+	}
+
+	/// Returns how many times the coroutine was polled.
+	///
+	/// Frames do not pass while the coroutine is paused.
+	#[func]
+	pub fn get_elapsed_frames(&self) -> i64 {
+		self.driver.elapsed_frames
+	}
+
+	/// Returns how much of the current wait already passed, from 0.0 (just started) to 1.0 (about to resume).
+	///
+	/// Only timed yields ([seconds](crate::prelude::seconds), [frames](crate::prelude::frames)) have a measurable progress,
+	/// returns -1.0 if the coroutine is waiting on a custom yield or isn't waiting at all.
+	///
+	/// Useful for driving UI directly off the coroutine, e.g. a cooldown ring.
+	#[func]
+	pub fn get_current_wait_progress(&self) -> f32 {
+		let remaining =
+			match &self.driver.last_yield {
+				Some(SpireYield::Seconds(_)) => self.driver.seconds_deadline - self.driver.elapsed_seconds,
+				Some(SpireYield::SecondsUnscaled(remaining)) => *remaining,
+				Some(SpireYield::Frames(_)) => self.driver.remaining_frames() as f64,
+				Some(SpireYield::Dyn(_) | SpireYield::Inline(_)) | None => return -1.0,
+			};
+
+		if self.driver.wait_total <= 0.0 {
+			return 1.0;
+		}
+
+		(1.0 - remaining / self.driver.wait_total).clamp(0.0, 1.0) as f32
+	}
+
+	/// Returns how many seconds the coroutine will keep waiting, if it's waiting on [seconds](crate::prelude::seconds)
+	/// or [seconds_unscaled](crate::prelude::seconds_unscaled), the latter in real seconds.
+	///
+	/// Returns `None` if the coroutine is waiting on anything else, or isn't waiting at all.
+	/// From GDScript, see [describe_wait](Self::describe_wait).
+	pub fn remaining_seconds(&self) -> Option<f64> {
+		match self.driver.last_yield {
+			Some(SpireYield::Seconds(_)) => Some((self.driver.seconds_deadline - self.driver.elapsed_seconds).max(0.0)),
+			Some(SpireYield::SecondsUnscaled(remaining)) => Some(remaining.max(0.0)),
+			_ => None,
+		}
+	}
+
+	/// Returns how many more polls the coroutine will keep waiting for, if it's waiting on [frames](crate::prelude::frames).
+	///
+	/// The poll that ends the wait isn't counted, e.g. right after yielding `frames(3)`, this returns 2.
+	/// Returns `None` if the coroutine is waiting on anything else, or isn't waiting at all.
+	pub fn remaining_frames(&self) -> Option<i64> {
+		match self.driver.last_yield {
+			Some(SpireYield::Frames(_)) => Some(self.driver.remaining_frames()),
+			_ => None,
+		}
+	}
+
+	/// Describes what the coroutine is currently waiting on, in a single GDScript-friendly call.
+	///
+	/// The dictionary always has the same keys:
+	/// - `kind`: `"frames"`, `"seconds"`, `"seconds_unscaled"`, `"dyn"` (custom yields, e.g. [wait_until](crate::prelude::wait_until))
+	///   or `"none"` (not waiting).
+	/// - `remaining`: the frames (`int`) or seconds (`float`, real seconds for `"seconds_unscaled"`) left to wait,
+	///   `null` for `"dyn"` and `"none"`.
+	/// - `progress`: same as [get_current_wait_progress](Self::get_current_wait_progress).
+	#[func]
+	pub fn describe_wait(&self) -> Dictionary {
+		let (kind, remaining) =
+			match &self.driver.last_yield {
+				Some(SpireYield::Frames(_)) => ("frames", self.driver.remaining_frames().to_variant()),
+				Some(SpireYield::Seconds(_)) => ("seconds", (self.driver.seconds_deadline - self.driver.elapsed_seconds).max(0.0).to_variant()),
+				Some(SpireYield::SecondsUnscaled(remaining)) => ("seconds_unscaled", remaining.max(0.0).to_variant()),
+				Some(SpireYield::Dyn(_) | SpireYield::Inline(_)) => ("dyn", Variant::nil()),
+				None => ("none", Variant::nil()),
+			};
+
+		dict! {
+			"kind": kind,
+			"remaining": remaining,
+			"progress": self.get_current_wait_progress(),
+		}
+	}
+
+	/// Returns a human-readable description of what the coroutine is currently waiting on, 
+	/// e.g. `"waiting on signal 'pressed' of <Button#123>"` or `"waiting on 2 of 3 coroutines"`.
+	///
+	/// Custom yields are described by their [KeepWaiting::describe](crate::prelude::KeepWaiting::describe) implementation,
+	/// yields that don't implement it are described generically. Returns `"not waiting"` if the coroutine isn't waiting on anything.
+	///
+	/// Useful for finding out why a coroutine is stuck, e.g. in a debug panel.
+	#[func]
+	pub fn get_awaiting_description(&self) -> String {
+		match &self.driver.last_yield {
+			Some(SpireYield::Frames(_)) => SpireYield::Frames(self.driver.remaining_frames()).describe(),
+			Some(_yield) => _yield.describe(),
+			None => "not waiting".to_string(),
+		}
+	}
+
+	/// Returns every [metadata](Object::get_meta) entry of the coroutine's node, 
+	/// including the ones added with [CoroutineBuilder::with_metadata](crate::prelude::CoroutineBuilder::with_metadata).
+	#[func]
+	pub fn get_metadata(&self) -> Dictionary {
+		let base = self.base();
+		let mut metadata = Dictionary::new();
+
+		for key in base.get_meta_list().iter_shared() {
+			metadata.set(key.clone(), base.get_meta(&key));
+		}
+
+		metadata
+	}
+
+	/// Returns true if the coroutine runs an async task (e.g. spawned with [StartAsyncTask](crate::prelude::StartAsyncTask)),
+	/// false if it runs a Rust coroutine.
+	///
+	/// [Killing](Self::kill) an async coroutine cancels its background task.
+	#[func]
+	pub fn is_async(&self) -> bool {
+		self.is_async
+	}
+
+	/// Returns how the coroutine ended, or [FinishReason::Running] if it hasn't ended yet.
+	///
+	/// Unlike the [finished](SIGNAL_FINISHED) signal, which is only emitted for [completed](FinishReason::Completed) coroutines (and killed ones with a kill default), 
+	/// this tells apart every way a coroutine can end. Since ended coroutines are queued for deletion, 
+	/// either query it during the frame the coroutine ended, or spawn the coroutine with [keep_alive](crate::prelude::CoroutineBuilder::keep_alive).
+	///
+	/// A coroutine [killed](Self::kill) while it had an [emit_on_kill](crate::prelude::CoroutineBuilder::emit_on_kill) default 
+	/// emits [finished](SIGNAL_FINISHED), but is still reported as [FinishReason::Killed].
+	#[func]
+	pub fn get_finish_reason(&self) -> FinishReason {
+		self.finish_reason
+	}
+
+	/// Returns the coroutine's result if it finished and was spawned with [keep_alive](crate::prelude::CoroutineBuilder::keep_alive), 
+	/// `nil` otherwise.
+	///
+	/// Does not consume the result, it can be read any number of times.
+	#[func]
+	pub fn try_get_result(&self) -> Variant {
+		self.result.clone().unwrap_or_default()
+	}
+
+	/// Returns true if [try_get_result](Self::try_get_result) has a result to return, 
+	/// which requires the coroutine to have finished (not killed) with [keep_alive](crate::prelude::CoroutineBuilder::keep_alive) set.
+	#[func]
+	pub fn is_result_ready(&self) -> bool {
+		self.result.is_some()
+	}
+
+	/// Connects `f` to the coroutine's [finished](SIGNAL_FINISHED) signal, converting the result into `R` before invoking it.
+	///
+	/// Logs an error instead of invoking `f` if the result can't be converted into `R`.
+	///
+	/// Unlike [on_finish](crate::prelude::CoroutineBuilder::on_finish), this can be used after spawning, 
+	/// but keep in mind that connecting to a coroutine that already finished does nothing.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_connect_finished(node: Gd<Node>) {
+	///     let coroutine =
+	///         node.start_coroutine(
+	///             #[coroutine] || {
+	///                 yield seconds(2.0);
+	///                 return "Hello, I'm 2 seconds late!";
+	///             });
+	///
+	///     coroutine.bind().connect_finished(|result: GString| {
+	///         godot_print!("{result}");
+	///     });
+	/// }
+	/// ```
+	pub fn connect_finished<R: FromGodot>(&self, mut f: impl 'static + FnMut(R)) {
+		let callable =
+			Callable::from_local_fn("connect_finished", move |args| {
+				let result = args.first().map_or_else(Variant::nil, |var| (*var).clone());
+
+				match result.try_to::<R>() {
+					Ok(result) => f(result),
+					Err(err) => godot_error!("{err}"),
+				}
+
+				Ok(Variant::nil())
+			});
+
+		self.to_gd().connect(SIGNAL_FINISHED, &callable);
+	}
+
+	/// Returns the [PollMode] the coroutine was spawned with.
+	///
+	/// The coroutine's Godot [ProcessMode](godot::classes::node::ProcessMode) can be read with [Node::get_process_mode].
+	pub fn get_poll_mode(&self) -> PollMode {
+		self.poll_mode
+	}
+
+	/// Returns the multiplier applied to the delta time of every poll, see [CoroutineBuilder::time_scale](crate::prelude::CoroutineBuilder::time_scale).
+	#[func]
+	pub fn get_time_scale(&self) -> f64 {
+		self.time_scale
+	}
+
+	/// Changes the multiplier applied to the delta time of every poll, negative values are clamped to 0.
+	///
+	/// Scales the delta of [process](INode::process), [physics_process](INode::physics_process) and [tool_step](Self::tool_step),
+	/// while [step](Self::step), [advance_time](Self::advance_time) and similar methods still use the exact time given to them.
+	#[func]
+	pub fn set_time_scale(&mut self, time_scale: f64) {
+		self.time_scale = time_scale.max(0.0);
+	}
+
+	/// Returns true if the coroutine is polled on [physics_process](INode::physics_process), 
+	/// false if it's polled on [process](INode::process).
+	///
+	/// GDScript-friendly version of [get_poll_mode](Self::get_poll_mode).
+	#[func]
+	pub fn get_poll_mode_is_physics(&self) -> bool {
+		self.poll_mode == PollMode::Physics
+	}
+
+	/// Cancels whatever the coroutine is currently waiting on (frames, seconds or a custom yield),
+	/// so that its closure is resumed on the next poll.
+	///
+	/// Only the *current* wait is skipped, the following yields are waited on as usual. 
+	/// Does not resume a paused coroutine.
+	///
+	/// Useful for skippable sequences, e.g. fast-forwarding a dialogue typewriter when the player presses a button.
+	#[func]
+	pub fn skip_wait(&mut self) {
+		self.driver.last_yield = None;
+		self.dyn_polls = 0;
 	}
 
 	/// Resumes the coroutine.
@@ -105,6 +599,64 @@ impl SpireCoroutine {
 	#[func]
 	pub fn resume(&mut self) {
 		self.paused = false;
+		self.start_gate = None;
+	}
+
+	/// Starts a coroutine that was spawned paused, e.g. with [spawn_paused](crate::prelude::CoroutineBuilder::spawn_paused).
+	///
+	/// Behaves exactly like [resume](Self::resume), it only exists to make deferred starts read better.
+	#[func]
+	pub fn start(&mut self) {
+		self.resume();
+	}
+
+	/// Runs the coroutine again from the beginning, with a closure rebuilt by the factory given to
+	/// [restartable](crate::prelude::CoroutineBuilder::restartable).
+	///
+	/// Discards the current wait and resets the elapsed time, a paused coroutine is also resumed.
+	/// Logs an error and does nothing if the coroutine wasn't spawned as restartable.
+	///
+	/// Ended coroutines can only be restarted if spawned with [keep_alive](crate::prelude::CoroutineBuilder::keep_alive),
+	/// since otherwise their node is de-spawned. Callbacks consumed when the coroutine ended
+	/// (e.g. [on_finish](crate::prelude::CoroutineBuilder::on_finish)) are not invoked again, 
+	/// unlike those registered with [on_each_finish](crate::prelude::CoroutineBuilder::on_each_finish).
+	///
+	/// Cannot be called from inside the coroutine's own closure.
+	#[func]
+	pub fn restart(&mut self) {
+		let Some(make) = &self.restart_factory
+		else {
+			godot_error!("Cannot restart coroutine `{}`: it wasn't spawned as restartable, see `CoroutineBuilder::restartable`.",
+			             self.base().get_name());
+			return;
+		};
+
+		if self.polling {
+			godot_error!("Cannot restart coroutine `{}` from inside its own closure.", self.base().get_name());
+			return;
+		}
+
+		if self.ended && !self.keep_alive {
+			godot_error!("Cannot restart coroutine `{}`: it already ended and wasn't spawned with `keep_alive`.",
+			             self.base().get_name());
+			return;
+		}
+
+		self.driver = CoroutineDriver::from_routine(make());
+		self.paused = false;
+		self.start_gate = None;
+		self.dyn_polls = 0;
+
+		if self.ended {
+			stats::on_spawn();
+			self.ended = false;
+			self.finish_reason = FinishReason::Running;
+			self.result = None;
+
+			let mut base = self.base_mut();
+			base.set_process(true);
+			base.set_physics_process(true);
+		}
 	}
 
 	/// Pauses the coroutine, ensuring it won't execute any instructions until it is resumed.
@@ -115,21 +667,89 @@ impl SpireCoroutine {
 		self.paused = true;
 	}
 
+	/// Moves the coroutine to the scene tree's root, it keeps running from where it was.
+	///
+	/// Afterward, the coroutine is no longer tied to the lifetime of its previous parent,
+	/// so it keeps running even if that parent is freed.
+	///
+	/// If called from inside the coroutine's own closure, the move is deferred until the end of the frame.
+	#[func]
+	pub fn detach_to_root(&mut self) {
+		let Some(root) = self.base().get_tree().and_then(|tree| tree.get_root())
+		else {
+			godot_error!("Cannot detach coroutine to root: coroutine is not inside a scene tree.");
+			return;
+		};
+
+		if self.polling {
+			self.base_mut().call_deferred("detach_to_root", &[]);
+			return;
+		}
+
+		let root = root.upcast::<Node>();
+		let mut base = self.base().to_godot();
+
+		if base.get_parent().as_ref() != Some(&root) {
+			base.reparent(&root);
+		}
+	}
+
 	/// Forces the coroutine to finish immediately.
 	///
 	/// Does not trigger the `finished` signal, the result is returned directly.
 	///
 	/// Be careful, running all the instructions in a coroutine at once can lead to unexpected results.
+	/// Coroutines can check [is_forcing](Self::is_forcing) to skip visual-only waits while being force-completed.
 	#[func]
 	pub fn force_run_to_completion(&mut self) -> Variant {
+		forcing(|| self.run_to_completion())
+	}
+
+	/// Forces the coroutine to finish immediately, simulating frames that last `step_seconds` each.
+	///
+	/// Unlike [force_run_to_completion](Self::force_run_to_completion), waits are honored: 
+	/// the coroutine is polled up to `max_iters` times, and time-based yields advance by `step_seconds` on each poll.
+	/// E.g. with a step of 0.1, a 3-second animation is simulated over 30 steps, instead of resuming with no time passed.
+	///
+	/// Triggers the `finished` signal as usual, and returns the result.
+	/// Returns `nil` (after logging an error) if the coroutine didn't finish within `max_iters` polls.
+	///
+	/// Like [step](Self::step), this ignores whether the coroutine is paused, and its [PollMode].
+	/// Coroutines can check [is_forcing](Self::is_forcing) to skip visual-only waits while being force-completed.
+	#[func]
+	pub fn force_run_to_completion_with_step(&mut self, step_seconds: f64, max_iters: u32) -> Variant {
+		forcing(|| {
+			for _ in 0..max_iters {
+				if self.is_finished() {
+					return Variant::nil();
+				}
+
+				if let Some(result) = self.run(step_seconds) {
+					return result;
+				}
+			}
+
+			if !self.is_finished() {
+				godot_error!("Coroutine `{}` did not complete after {max_iters} steps of {step_seconds} seconds.", self.base().get_name());
+			}
+
+			Variant::nil()
+		})
+	}
+
+	fn run_to_completion(&mut self) -> Variant {
 		let mut iters_remaining = 4096;
 
 		loop {
 			match self.resume_closure() {
+				Ok(_) if self.ended => {
+					// Killed by its own closure.
+					return Variant::nil();
+				}
 				Ok(state) => {
 					match state {
 						// keep going
-						CoroutineState::Yielded(_) => {
+						RoutineState::Yielded(_) => {
 							iters_remaining -= 1;
 							if iters_remaining > 0 {
 								continue;
@@ -139,12 +759,17 @@ impl SpireCoroutine {
 								return Variant::nil();
 							}
 						}
-						CoroutineState::Complete(result) => {
-							self.de_spawn();
+						RoutineState::Complete(result) => {
+							self.set_finish_reason(FinishReason::Completed);
+							self.end();
 							return result;
 						}
 					}
 				}
+				Err(_) if !self.ended => {
+					// Restarted after panicking.
+					continue;
+				}
 				Err(_) => {
 					return Variant::nil();
 				}
@@ -154,130 +779,621 @@ impl SpireCoroutine {
 
 	/// De-spawns the coroutine.
 	///
-	/// Does not trigger the `finished` signal.
+	/// Invokes the callbacks registered with [on_cancel](crate::prelude::CoroutineBuilder::on_cancel).
+	/// Does not trigger the `finished` signal, unless the coroutine was spawned with 
+	/// [emit_on_kill](crate::prelude::CoroutineBuilder::emit_on_kill), in which case it then finishes with the configured default.
+	///
+	/// If called from inside the coroutine's own closure, the closure keeps executing until its next yield,
+	/// then it is never resumed again. The de-spawning is deferred until the current poll ends.
+	///
+	/// The closure is dropped when de-spawning, which also cancels the background task of [async](Self::is_async) coroutines.
 	#[func]
 	pub fn kill(&mut self) {
-		self.de_spawn();
+		if self.ended {
+			return;
+		}
+
+		self.set_finish_reason(FinishReason::Killed);
+
+		for call in std::mem::take(&mut self.calls_on_cancel) {
+			call();
+		}
+
+		// Cloned rather than taken, so that it also applies to the runs started by `restart`.
+		if let Some(default) = self.kill_result.clone() {
+			self.finish_with(default);
+		} else {
+			self.end();
+		}
 	}
 
 	/// De-spawns the coroutine.
 	///
 	/// Triggers the `finished` signal with `result` as the argument.
+	///
+	/// Does nothing if the coroutine already finished or was killed.
+	///
+	/// In debug builds, logs a warning if `result` can't be converted to the return type expected by
+	/// the coroutine's [on_finish](crate::prelude::CoroutineBuilder::on_finish) callbacks.
+	///
+	/// `result` goes through the coroutine's [map_result](crate::prelude::CoroutineBuilder::map_result) mappings (if any)
+	/// before reaching the callbacks and the signal.
+	///
+	/// If called from inside the coroutine's own closure, the closure keeps executing until its next yield,
+	/// then it is never resumed again. The de-spawning is deferred until the current poll ends.
+	///
+	/// # State during finish handlers
+	/// The [on_finish](crate::prelude::CoroutineBuilder::on_finish) callbacks are invoked first, then [finished](SIGNAL_FINISHED) is emitted.
+	/// While they run, the coroutine's node:
+	/// - Is still inside the tree, under the same parent, and is not queued for deletion.
+	/// - Can be read through its handle, e.g. [get_elapsed_seconds](Self::get_elapsed_seconds) or [get_metadata](Self::get_metadata).
+	/// - Already counts as [finished](Self::is_finished), calling [kill](Self::kill) or `finish_with` again does nothing.
+	///
+	/// The node is de-spawned right after the last handler returns.
 	#[func]
 	pub fn finish_with(&mut self, result: Variant) {
-		for call in self.calls_on_finish.drain(..) {
-			match call {
-				OnFinishCall::Closure(closure) => {
-					closure(result.clone());
-				}
-				OnFinishCall::Callable(callable) => {
-					if callable.is_valid() {
-						callable.callv(&VariantArray::from(&[result.clone()]));
+		if self.ended {
+			return;
+		}
+
+		#[cfg(debug_assertions)]
+		if let Some(check) = self.result_check {
+			if !(check.converts)(&result) {
+				godot_warn!("`finish_with` was called with `{result:?}`, which cannot be converted to the coroutine's return type `{}`",
+					check.type_name);
+			}
+		}
+
+		self.calls_on_cancel.clear();
+
+		let result =
+			match &self.result_map {
+				Some(map) => map(result),
+				None => result,
+			};
+
+		// Marked as ended before notifying, so that handlers see it as finished and cannot finish/kill it again.
+		stats::on_end();
+		self.ended = true;
+		self.set_finish_reason(FinishReason::Completed);
+
+		if self.keep_alive {
+			self.result = Some(result.clone());
+		}
+
+		let calls_on_finish = std::mem::take(&mut self.calls_on_finish);
+		let mut kept_calls = Vec::new();
+
+		{
+			// Holding the guard allows handlers to `bind`/`bind_mut` this coroutine through its handle.
+			let mut base = self.base_mut();
+
+			for call in calls_on_finish {
+				match call {
+					OnFinishCall::Closure(closure) => {
+						closure(result.clone());
+					}
+					OnFinishCall::Repeating(mut closure) => {
+						closure(result.clone());
+						kept_calls.push(OnFinishCall::Repeating(closure));
+					}
+					OnFinishCall::Callable(callable) => {
+						if callable.is_valid() {
+							callable.callv(&VariantArray::from(std::slice::from_ref(&result)));
+						}
 					}
 				}
 			}
+
+			base.emit_signal(SIGNAL_FINISHED, &[result]);
 		}
 
-		self.base_mut().emit_signal(SIGNAL_FINISHED, &[result]);
-		self.de_spawn();
+		self.calls_on_finish = kept_calls;
+
+		self.end();
+	}
+
+	fn end(&mut self) {
+		if !self.ended {
+			stats::on_end();
+		}
+
+		self.ended = true;
+		self.run_exit_calls();
+
+		// De-spawning in the middle of a poll would leave the node out of the tree while it's still executing,
+		// `run` de-spawns once the poll returns.
+		if !self.polling {
+			self.de_spawn();
+		}
+	}
+
+	/// Records why the coroutine ended, unless a reason was already recorded.
+	fn set_finish_reason(&mut self, reason: FinishReason) {
+		if self.finish_reason == FinishReason::Running {
+			self.finish_reason = reason;
+		}
+	}
+
+	fn run_exit_calls(&mut self) {
+		if self.calls_on_exit.is_empty() {
+			return;
+		}
+
+		let calls = std::mem::take(&mut self.calls_on_exit);
+		let _guard = self.base_mut();
+
+		for call in calls {
+			call();
+		}
 	}
 
 	fn de_spawn(&mut self) {
+		// Dropping the closure right away (instead of when the node is freed) cancels async tasks immediately.
+		self.driver.clear();
+
 		let mut base = self.base().to_godot();
 
+		if self.keep_alive {
+			base.set_process(false);
+			base.set_physics_process(false);
+			return;
+		}
+
 		if let Some(mut parent) = base.get_parent() {
 			parent.remove_child(&base)
 		}
 
+		if let Some(pool) = self.pool.take() {
+			self.reset_for_reuse();
+
+			if pool.try_put_idle(self.to_gd()).is_ok() {
+				return;
+			}
+		}
+
 		base.queue_free();
 	}
 
-	fn run(&mut self, delta_time: f64) {
-		if let Some(result) = self.poll(delta_time) {
-			self.finish_with(result);
+	/// Polls the coroutine once, as if a frame with `delta_time` had passed.
+	///
+	/// Ignores whether the coroutine is paused, and its [PollMode].
+	///
+	/// If the coroutine finishes during this step, it is finished as usual (triggering the `finished` signal)
+	/// and its result is returned.
+	pub fn step(&mut self, delta_time: f64) -> Option<Variant> {
+		self.run(delta_time)
+	}
+
+	/// Polls the coroutine once with `delta_time`, for driving coroutines where the engine doesn't, 
+	/// e.g. from the `_process` of `@tool` scripts and editor plugins.
+	///
+	/// [SpireCoroutine] isn't a tool class, so inside the editor its [process](INode::process)/[physics_process](INode::physics_process)
+	/// never run. Calling this from your own tool's `_process` (passing its delta) makes editor-time coroutines progress as they would in-game.
+	///
+	/// Unlike [step](Self::step), this respects [pause](Self::pause) and [start_when](crate::prelude::CoroutineBuilder::start_when) gates. 
+	/// Ignores the coroutine's [PollMode].
+	///
+	/// Returns the coroutine's result if it finished during this call, `nil` otherwise.
+	/// To run a coroutine to completion synchronously instead, see [block_until_finished].
+	#[func]
+	pub fn tool_step(&mut self, delta_time: f64) -> Variant {
+		if self.is_finished() {
+			return Variant::nil();
+		}
+
+		self.check_start_gate();
+
+		if self.paused {
+			return Variant::nil();
 		}
+
+		self.run(delta_time * self.time_scale).unwrap_or_default()
 	}
 
-	fn poll(&mut self, delta_time: f64) -> Option<Variant> {
-		match &mut self.last_yield {
-			Some(SpireYield::Frames(frames)) => {
-				if *frames > 0 {
-					*frames -= 1;
-					None
-				} else {
-					self.last_yield = None;
-					self.poll(delta_time)
+	/// Fast-forwards the coroutine by `seconds`, polling it in increments of at most `max_step_seconds`.
+	///
+	/// Bounding the increments ensures that condition-based yields (e.g. [wait_until](crate::prelude::wait_until))
+	/// are evaluated a reasonable number of times, instead of being jumped over by a single huge poll.
+	/// If `max_step_seconds` is not positive, `seconds` is consumed in a single poll.
+	///
+	/// Like [step](Self::step), this ignores whether the coroutine is paused, and its [PollMode].
+	///
+	/// Stops early and returns the result if the coroutine finishes, returns `None` if it's still running afterward.
+	///
+	/// Coroutines can check [is_forcing](Self::is_forcing) to know they are being fast-forwarded.
+	pub fn advance_time(&mut self, seconds: f64, max_step_seconds: f64) -> Option<Variant> {
+		let max_step = if max_step_seconds > 0.0 { max_step_seconds } else { seconds };
+		let mut remaining = seconds;
+
+		forcing(|| {
+			while remaining > 0.0 && !self.is_finished() {
+				let delta_time = remaining.min(max_step);
+				remaining -= delta_time;
+
+				if let Some(result) = self.run(delta_time) {
+					return Some(result);
 				}
 			}
-			Some(SpireYield::Seconds(seconds)) => {
-				if *seconds > delta_time {
-					*seconds -= delta_time;
-					None
-				} else {
-					let seconds = *seconds; // Deref needed to un-borrow self.last_yield
-					self.last_yield = None;
-					self.poll(delta_time - seconds)
+
+			None
+		})
+	}
+
+	/// Runs the coroutine to completion right now, honoring its custom yields (e.g. [wait_until](crate::prelude::wait_until)) 
+	/// by re-evaluating them in a loop, with zero delta time.
+	///
+	/// Unlike [force_run_to_completion](Self::force_run_to_completion), waits are not ignored, 
+	/// and unlike [advance_time](Self::advance_time), time does not pass.
+	///
+	/// Polls the coroutine at most `max_iters` times, stops early and logs an error if:
+	/// - It's waiting on [seconds](crate::prelude::seconds) or on more than one [frame](crate::prelude::frames), 
+	///   which can't pass with zero delta time.
+	/// - A custom yield kept waiting for a whole poll without the coroutine making progress, meaning it's genuinely blocked.
+	///
+	/// Returns the result if the coroutine finished (triggering the `finished` signal as usual), `None` otherwise.
+	///
+	/// Like [step](Self::step), this ignores whether the coroutine is paused, and its [PollMode].
+	/// Coroutines can check [is_forcing](Self::is_forcing) to skip visual-only waits while being driven.
+	pub fn drive_to_completion(&mut self, max_iters: u32) -> Option<Variant> {
+		forcing(|| {
+			for _ in 0..max_iters {
+				if self.is_finished() {
+					return None;
 				}
+
+				if let Some(result) = self.run(0.0) {
+					return Some(result);
+				}
+
+				let blocked_on =
+					match &self.driver.last_yield {
+						Some(SpireYield::Seconds(seconds)) => Some(format!("a wait of {seconds} seconds")),
+						Some(SpireYield::SecondsUnscaled(seconds)) => Some(format!("a wait of {seconds} unscaled seconds")),
+						Some(SpireYield::Frames(_)) if self.driver.remaining_frames() > 0 => {
+							Some(format!("a wait of {} more frames", self.driver.remaining_frames()))
+						}
+						Some(SpireYield::Dyn(_) | SpireYield::Inline(_)) if self.dyn_polls > 1 => {
+							Some("a custom yield that made no progress across a whole poll".to_string())
+						}
+						_ => None,
+					};
+
+				if let Some(blocked_on) = blocked_on {
+					godot_error!("Cannot drive coroutine `{}` to completion: it is blocked on {blocked_on}.", self.base().get_name());
+					return None;
+				}
+			}
+
+			if !self.is_finished() {
+				godot_error!("Coroutine `{}` did not complete after being driven for {max_iters} iterations.", self.base().get_name());
 			}
-			Some(SpireYield::Dyn(dyn_yield)) => {
-				if dyn_yield.keep_waiting(delta_time) {
-					None
-				} else {
-					self.last_yield = None;
-					self.poll(delta_time)
+
+			None
+		})
+	}
+
+	fn run(&mut self, delta_time: f64) -> Option<Variant> {
+		self.driver.begin_frame(delta_time);
+		self.steps_left = self.max_steps_per_frame;
+
+		if !self.calls_on_each_frame.is_empty() {
+			// Moved out so that the callbacks can re-entrantly `bind` this coroutine through its handle.
+			let mut calls = std::mem::take(&mut self.calls_on_each_frame);
+
+			{
+				let _guard = self.base_mut();
+				for call in &mut calls {
+					call(delta_time);
 				}
 			}
-			None => {
-				let state = self.resume_closure().ok()?;
-				
-				match state {
-					CoroutineState::Yielded(next_yield) => {
-						self.last_yield = Some(next_yield);
-						self.poll(delta_time)
+
+			self.calls_on_each_frame = calls;
+		}
+
+		let was_polling = std::mem::replace(&mut self.polling, true);
+		let poll_result = self.poll(delta_time);
+		self.polling = was_polling;
+
+		if self.ended {
+			// Killed or finished by its own closure during the poll.
+			if !self.polling {
+				self.de_spawn();
+			}
+
+			return None;
+		}
+
+		if let Some(result) = poll_result {
+			self.finish_with(result.clone());
+			Some(result)
+		} else {
+			if self.emit_waiting {
+				let elapsed = self.driver.elapsed_seconds.to_variant();
+				self.base_mut().emit_signal(SIGNAL_WAITING, &[elapsed]);
+			}
+
+			None
+		}
+	}
+
+	fn poll(&mut self, delta_time: f64) -> Option<Variant> {
+		let mut delta_time = delta_time;
+
+		loop {
+			match self.driver.poll_wait(delta_time) {
+				WaitPoll::Pending => {
+					if let Some(SpireYield::Dyn(_) | SpireYield::Inline(_)) = &self.driver.last_yield {
+						self.dyn_polls += 1;
+
+						if self.stuck_threshold == Some(self.dyn_polls) {
+							godot_warn!("Coroutine `{}` has been waiting on the same custom yield for {} polls, \
+							             its condition may never be satisfied (e.g. it references a freed object).",
+							            self.base().get_name(), self.dyn_polls);
+						}
+					}
+
+					return None;
+				}
+				WaitPoll::Abort => {
+					self.kill();
+					return None;
+				}
+				WaitPoll::Ready(unconsumed_delta) => {
+					self.dyn_polls = 0;
+					delta_time = unconsumed_delta;
+				}
+			}
+
+			if self.steps_left == 0 {
+				return None;
+			}
+
+			self.steps_left -= 1;
+			let state = self.resume_closure().ok()?;
+
+			if self.ended {
+				return None;
+			}
+
+			match state {
+				RoutineState::Yielded(next_yield) => {
+					#[cfg(debug_assertions)]
+					if let SpireYield::Frames(frames @ ..0) = next_yield {
+						godot_warn!("Coroutine `{}` yielded a negative amount of frames ({frames}), it'll be treated as 0.",
+						            self.base().get_name());
 					}
-					CoroutineState::Complete(result) => {
-						Some(result)
+
+					self.driver.set_yield(next_yield, delta_time);
+
+					if let Some(records) = &self.yield_records {
+						let recorded = self.driver.last_yield.as_ref().map_or(YieldKind::Custom, YieldKind::of);
+
+						records.borrow_mut().push(YieldRecord {
+							kind: recorded,
+							elapsed_seconds: self.driver.elapsed_seconds - delta_time,
+							elapsed_frames: self.driver.elapsed_frames,
+						});
 					}
 				}
+				RoutineState::Complete(result) => {
+					return Some(result);
+				}
 			}
 		}
 	}
 
-	fn resume_closure(&mut self) -> Result<CoroutineState<SpireYield, Variant>, ()> {
-		let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
-			let mut pin = Pin::new(&mut self.coroutine);
-			let yield_result = pin.as_mut().resume(());
-			yield_result
-		}));
+	fn resume_closure(&mut self) -> Result<RoutineState<Variant>, ()> {
+		// The closure is moved out of `self` so that it can be resumed while holding the `base_mut` guard,
+		// which allows the closure to re-entrantly `bind`/`bind_mut` this coroutine through its own handle.
+		let mut coroutine = std::mem::replace(&mut self.driver.routine, CoroutineDriver::idle().routine);
+
+		let previous = CURRENT.replace(Some(self.to_gd()));
+
+		let result = {
+			let _guard = self.base_mut();
+
+			std::panic::catch_unwind(AssertUnwindSafe(|| coroutine.resume()))
+		};
+
+		CURRENT.set(previous);
 		
 		match result {
-			Ok(state) => Ok(state),
+			Ok(state) => {
+				self.driver.routine = coroutine;
+				Ok(state)
+			}
 			Err(err) => {
-				let dummy = Box::new(#[coroutine] || { Variant::nil() });
-
 				// If the coroutine's closure panicked, we cannot drop it as any destructors it has would be run with invalid state.
-				let must_leak = std::mem::replace(&mut self.coroutine, dummy);
-				Box::leak(must_leak);
+				Box::leak(coroutine);
 
+				let restart = self.panic_restart.as_mut().filter(|restart| restart.restarts_left > 0);
+
+				if let (Some(restart), Some(make)) = (restart, &self.restart_factory) {
+					restart.restarts_left -= 1;
+					self.driver.routine = make();
+					self.driver.last_yield = None;
+
+					godot_warn!("Coroutine's closure panicked, restarting it on the next poll ({} restarts left). The panicked closure was leaked.\n\
+								 Panic Reason: \"{}\"", restart.restarts_left, crate::panic_reason(&err));
+					return Err(());
+				}
+
+				self.set_finish_reason(FinishReason::Panicked);
 				self.kill();
 				
-				let reason: &dyn std::fmt::Debug = 
-					if let Some(str) = err.downcast_ref::<&str>() {
-						str
-					} else if let Some(string) = err.downcast_ref::<String>() {
-						string
-					} else {
-						&err
-					};
-
 				godot_error!("Coroutine's closure panicked, the SpireCoroutine will now self-destruct and leak the closure.\n\
-							  Panic Reason: \"{reason:?}\"");
+							  Panic Reason: \"{}\"", crate::panic_reason(&err));
 				Err(())
 			}
 		}
 	}
 }
 
+/// Synchronously steps `coroutine` until it finishes, returning its result.
+///
+/// Meant for tests, where it allows asserting on a coroutine's result without connecting to signals.
+///
+/// Each step simulates a frame lasting `1 / physics_ticks_per_second` seconds, regardless of the coroutine's [PollMode].
+/// See [SpireCoroutine::step].
+///
+/// Returns `None` if:
+/// - The coroutine did not finish after `max_frames` steps.
+/// - The coroutine was already finished (or freed) before the call.
+/// - The coroutine ended abnormally, e.g. its closure panicked.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_block_until_finished(node: Gd<Node>) {
+///     let coroutine = 
+///         node.start_coroutine(
+///             #[coroutine] || {
+///                 yield frames(5);
+///                 10
+///             });
+///
+///     let result = block_until_finished(coroutine, 100);
+///     assert_eq!(result, Some(10.to_variant()));
+/// }
+/// ```
+pub fn block_until_finished(mut coroutine: Gd<SpireCoroutine>, max_frames: u32) -> Option<Variant> {
+	let delta_time = 1.0 / godot::classes::Engine::singleton().get_physics_ticks_per_second() as f64;
+
+	for _ in 0..max_frames {
+		if coroutine.is_finished() {
+			return None;
+		}
+
+		if let Some(result) = coroutine.bind_mut().step(delta_time) {
+			return Some(result);
+		}
+	}
+
+	None
+}
+
+impl SpireCoroutine {
+	/// Creates a coroutine with default settings, whose closure returns immediately.
+	pub(crate) fn blank(base: Base<Node>) -> Self {
+		SpireCoroutine {
+			base,
+			driver: CoroutineDriver::idle(),
+			poll_mode: PollMode::Process,
+			paused: false,
+			is_async: false,
+			calls_on_finish: Vec::new(),
+			calls_on_each_frame: Vec::new(),
+			calls_on_cancel: Vec::new(),
+			calls_on_exit: Vec::new(),
+			panic_restart: None,
+			restart_factory: None,
+			kill_result: None,
+			start_gate: None,
+			result_check: None,
+			result_map: None,
+			emit_waiting: false,
+			time_scale: 1.0,
+			max_steps_per_frame: u32::MAX,
+			steps_left: 0,
+			polling: false,
+			ended: false,
+			dyn_polls: 0,
+			stuck_threshold: None,
+			pool: None,
+			group: None,
+			keep_alive: false,
+			result: None,
+			yield_records: None,
+			mirrored_node: None,
+			finish_reason: FinishReason::Running,
+			generation: 0,
+			spawn_sequence: 0,
+		}
+	}
+
+	/// Resumes the coroutine if its start gate opened.
+	fn check_start_gate(&mut self) {
+		if let Some(gate) = &mut self.start_gate {
+			if gate() {
+				self.resume();
+			}
+		}
+	}
+
+	/// Returns false if the coroutine mirrors a node's processing and that node isn't processing,
+	/// on the process callback that matches the coroutine's [PollMode].
+	///
+	/// A freed mirrored node doesn't block the coroutine.
+	fn mirrored_node_is_processing(&self) -> bool {
+		let Some(node) = &self.mirrored_node
+		else { return true };
+
+		if !node.is_instance_valid() {
+			return true;
+		}
+
+		let processing =
+			match self.poll_mode {
+				PollMode::Process => node.is_processing(),
+				PollMode::Physics => node.is_physics_processing(),
+			};
+
+		processing && node.can_process()
+	}
+
+	/// Clears the state of an ended coroutine, so that its node can be reused by a [CoroutinePool].
+	///
+	/// The coroutine stays [ended](Self::is_finished) until it's spawned again.
+	fn reset_for_reuse(&mut self) {
+		self.driver = CoroutineDriver::idle();
+		self.calls_on_finish.clear();
+		self.calls_on_each_frame.clear();
+		self.calls_on_cancel.clear();
+		self.calls_on_exit.clear();
+		self.panic_restart = None;
+		self.restart_factory = None;
+		self.kill_result = None;
+		self.start_gate = None;
+		self.result_check = None;
+		self.result_map = None;
+		self.steps_left = 0;
+		self.dyn_polls = 0;
+		self.generation += 1;
+
+		let mut base = self.base().to_godot();
+
+		if let Some(group) = self.group.take() {
+			base.remove_from_group(&group);
+		}
+
+		// Otherwise a name given with `CoroutineBuilder::named` would stick to the next coroutine reusing the node.
+		let generated_name = GString::from(format!("@SpireCoroutine@{}", base.instance_id()));
+		base.set_name(&generated_name);
+
+		self.result = None;
+		self.yield_records = None;
+		self.mirrored_node = None;
+		self.finish_reason = FinishReason::Running;
+
+		for key in base.get_meta_list().iter_shared() {
+			base.remove_meta(&key);
+		}
+
+		for signal in [SIGNAL_FINISHED, SIGNAL_WAITING, SIGNAL_VALUE] {
+			for connection in base.get_signal_connection_list(signal).iter_shared() {
+				if let Some(callable) = connection.get("callable").and_then(|var| var.try_to::<Callable>().ok()) {
+					base.disconnect(signal, &callable);
+				}
+			}
+		}
+	}
+}
+
 pub trait IsRunning {
 	/// See [SpireCoroutine::is_running]
 	fn is_running(&self) -> bool;
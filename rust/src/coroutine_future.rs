@@ -0,0 +1,125 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use godot::prelude::*;
+
+use crate::OnFinishCall;
+use crate::prelude::*;
+
+/// A [Future] that resolves once a coroutine ends, with the coroutine's result converted into `R`.
+///
+/// Allows awaiting coroutines from async tasks, e.g. the ones started with [start_async_task](StartAsyncTask::start_async_task).
+///
+/// Resolves with `None` if the coroutine ended without finishing normally (e.g. it was [killed](SpireCoroutine::kill),
+/// panicked, or was freed along with its owner), or if its result couldn't be converted into `R`.
+///
+/// The result is converted on the main thread, when the coroutine finishes,
+/// so the future only holds `R` and can be awaited on any thread.
+/// The future itself must be created on the main thread.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_coroutine_future(node: Gd<Node>) {
+///     let loading =
+///         node.start_coroutine(
+///             #[coroutine] || {
+///                 yield seconds(1.0);
+///                 42
+///             });
+///
+///     let loaded = CoroutineFuture::<i32>::new(&loading);
+///
+///     node.start_async_task(async move {
+///         let result = loaded.await;
+///         result == Some(42)
+///     });
+/// }
+/// ```
+pub struct CoroutineFuture<R: FromGodot + Send> {
+	state: Arc<Mutex<State<R>>>,
+}
+
+struct State<R> {
+	ended: bool,
+	result: Option<R>,
+	waker: Option<Waker>,
+}
+
+impl<R: 'static + FromGodot + Send> CoroutineFuture<R> {
+	/// Creates a future that resolves once `coroutine` ends.
+	///
+	/// If `coroutine` already ended, the future resolves right away, with the coroutine's result if it was spawned with
+	/// [keep_alive](CoroutineBuilder::keep_alive), `None` otherwise.
+	pub fn new(coroutine: &Gd<SpireCoroutine>) -> Self {
+		let state = Arc::new(Mutex::new(State {
+			ended: false,
+			result: None,
+			waker: None,
+		}));
+
+		if !coroutine.is_instance_valid() {
+			state.lock().unwrap().ended = true;
+			return Self { state };
+		}
+
+		let mut coroutine = coroutine.clone();
+		let mut bind = coroutine.bind_mut();
+
+		if bind.is_finished() {
+			let mut state = state.lock().unwrap();
+			state.ended = true;
+			state.result = bind.result.as_ref().and_then(|result| result.try_to::<R>().ok());
+		} else {
+			let on_finish = state.clone();
+			bind.calls_on_finish.push(OnFinishCall::Closure(Box::new(move |result| {
+				// Converted here, on the main thread, so that the `Variant` never leaves it.
+				on_finish.lock().unwrap().result = result.try_to::<R>().ok();
+			})));
+
+			// Exit calls run after the finish calls, and also when the coroutine ends without finishing.
+			let on_exit = state.clone();
+			bind.calls_on_exit.push(Box::new(move || {
+				let waker = {
+					let mut state = on_exit.lock().unwrap();
+					state.ended = true;
+					state.waker.take()
+				};
+
+				if let Some(waker) = waker {
+					waker.wake();
+				}
+			}));
+		}
+
+		drop(bind);
+		Self { state }
+	}
+}
+
+impl<R: 'static + FromGodot + Send> From<Gd<SpireCoroutine>> for CoroutineFuture<R> {
+	fn from(coroutine: Gd<SpireCoroutine>) -> Self {
+		Self::new(&coroutine)
+	}
+}
+
+impl<R: FromGodot + Send> Future for CoroutineFuture<R> {
+	type Output = Option<R>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let mut state = self.state.lock().unwrap();
+
+		if state.ended {
+			Poll::Ready(state.result.take())
+		} else {
+			state.waker = Some(cx.waker().clone());
+			Poll::Pending
+		}
+	}
+}
@@ -0,0 +1,258 @@
+use std::panic::AssertUnwindSafe;
+
+use godot::prelude::*;
+
+use crate::builder::into_routine;
+use crate::routine::{PollRoutine, RoutineState};
+use crate::state_machine::{state_machine, Step};
+use crate::yielding::{count_down_unscaled, SpireYield, WaitOutcome};
+
+pub(crate) type Routine = Box<dyn PollRoutine<Return = Variant>>;
+
+/// The polling state machine of a coroutine, detached from the scene tree.
+///
+/// [SpireCoroutine](crate::prelude::SpireCoroutine) wraps a driver, adding everything that needs a node:
+/// signals, callbacks, pausing, and being polled by the engine.
+/// A driver alone is ticked manually instead, which allows running coroutine logic headless,
+/// e.g. in logic tests or server-side simulations without a scene.
+///
+/// Yields are handled exactly like in a [SpireCoroutine](crate::prelude::SpireCoroutine),
+/// where each [tick](Self::tick) counts as a frame lasting `delta_time` seconds.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_driver() {
+///     let mut driver =
+///         CoroutineDriver::new(
+///             #[coroutine] || {
+///                 yield frames(2);
+///                 yield seconds(1.0);
+///                 "done"
+///             });
+///
+///     let mut result = None;
+///
+///     while result.is_none() {
+///         result = driver.tick(0.25);
+///     }
+///
+///     assert_eq!(driver.elapsed_frames(), 6);
+///     assert_eq!(result, Some("done".to_variant()));
+/// }
+/// ```
+pub struct CoroutineDriver {
+	pub(crate) routine: Routine,
+	pub(crate) last_yield: Option<SpireYield>,
+	pub(crate) elapsed_seconds: f64,
+	pub(crate) elapsed_frames: i64,
+	/// Value of [elapsed_seconds](Self::elapsed_seconds) at which the current [SpireYield::Seconds] ends.
+	pub(crate) seconds_deadline: f64,
+	/// Value of [elapsed_frames](Self::elapsed_frames) at which the current [SpireYield::Frames] ends.
+	pub(crate) frames_deadline: i64,
+	/// The initial duration (in seconds or frames) of the current timed yield.
+	pub(crate) wait_total: f64,
+	/// Only set by [tick](Self::tick), [SpireCoroutine](crate::prelude::SpireCoroutine) tracks its own lifetime.
+	finished: bool,
+}
+
+/// The result of polling a driver's current wait, see [CoroutineDriver::poll_wait].
+pub(crate) enum WaitPoll {
+	/// The wait isn't over yet.
+	Pending,
+	/// The wait is over (or there was no wait), the closure should be resumed with the delta time the wait didn't consume.
+	Ready(f64),
+	/// The wait [aborted](WaitOutcome::Abort) the coroutine.
+	Abort,
+}
+
+impl CoroutineDriver {
+	/// Creates a driver that runs `f`, `f` doesn't run until the first [tick](Self::tick).
+	pub fn new<R: 'static + ToGodot>(
+		f: impl 'static + PollRoutine<Return = R>,
+	) -> Self {
+		Self::from_routine(into_routine(f))
+	}
+
+	pub(crate) fn from_routine(routine: Routine) -> Self {
+		Self {
+			routine,
+			last_yield: None,
+			elapsed_seconds: 0.0,
+			elapsed_frames: 0,
+			seconds_deadline: 0.0,
+			frames_deadline: 0,
+			wait_total: 0.0,
+			finished: false,
+		}
+	}
+
+	/// A driver whose closure returns immediately.
+	pub(crate) fn idle() -> Self {
+		Self::from_routine(Box::new(state_machine((), |_| Step::Done(Variant::nil()))))
+	}
+
+	/// Polls the coroutine once, as if a frame lasting `delta_time` seconds had passed.
+	///
+	/// Returns the coroutine's result on the tick it finishes, `None` before that and on any tick after it.
+	///
+	/// If the closure panics or a yield [aborts](WaitOutcome::Abort) it, the coroutine finishes with `nil`.
+	/// A panicked closure is leaked instead of dropped, like in [SpireCoroutine](crate::prelude::SpireCoroutine).
+	pub fn tick(&mut self, delta_time: f64) -> Option<Variant> {
+		if self.finished {
+			return None;
+		}
+
+		self.begin_frame(delta_time);
+		let mut delta_time = delta_time;
+
+		let result =
+			loop {
+				match self.poll_wait(delta_time) {
+					WaitPoll::Pending => return None,
+					WaitPoll::Abort => break Variant::nil(),
+					WaitPoll::Ready(unconsumed_delta) => delta_time = unconsumed_delta,
+				}
+
+				let state = std::panic::catch_unwind(AssertUnwindSafe(|| self.routine.resume()));
+
+				match state {
+					Ok(RoutineState::Yielded(next_yield)) => self.set_yield(next_yield, delta_time),
+					Ok(RoutineState::Complete(result)) => break result,
+					Err(err) => {
+						// The panicked closure cannot be safely dropped.
+						Box::leak(std::mem::replace(&mut self.routine, Self::idle().routine));
+
+						godot_error!("A driven coroutine panicked, it'll finish with `nil` and its closure will be leaked.\n\
+						              Panic Reason: \"{}\"", crate::panic_reason(&err));
+						break Variant::nil();
+					}
+				}
+			};
+
+		self.finished = true;
+		self.clear();
+		Some(result)
+	}
+
+	/// Returns true once the coroutine finished, see [tick](Self::tick).
+	pub fn is_finished(&self) -> bool {
+		self.finished
+	}
+
+	/// The total seconds the coroutine has been ticked for.
+	pub fn elapsed_seconds(&self) -> f64 {
+		self.elapsed_seconds
+	}
+
+	/// How many times the coroutine has been ticked.
+	pub fn elapsed_frames(&self) -> i64 {
+		self.elapsed_frames
+	}
+
+	/// Drops the closure and the current wait, without resetting the elapsed time.
+	pub(crate) fn clear(&mut self) {
+		self.routine = Self::idle().routine;
+		self.last_yield = None;
+	}
+
+	/// How many more polls the current [SpireYield::Frames] waits for, after the one that ends it.
+	///
+	/// Returns 0 if the coroutine isn't waiting on frames.
+	pub(crate) fn remaining_frames(&self) -> i64 {
+		match self.last_yield {
+			Some(SpireYield::Frames(_)) => (self.frames_deadline - self.elapsed_frames - 1).max(0),
+			_ => 0,
+		}
+	}
+
+	pub(crate) fn begin_frame(&mut self, delta_time: f64) {
+		self.elapsed_seconds += delta_time;
+		self.elapsed_frames += 1;
+	}
+
+	/// Polls the current wait, clearing it once it's over.
+	pub(crate) fn poll_wait(&mut self, delta_time: f64) -> WaitPoll {
+		match &mut self.last_yield {
+			Some(SpireYield::Frames(_)) => {
+				// Like with seconds, comparing against a deadline keeps long waits (e.g. `frames(6000)`) down to 
+				// a single comparison per poll, without touching the yield itself.
+				if self.elapsed_frames < self.frames_deadline {
+					return WaitPoll::Pending;
+				}
+
+				self.last_yield = None;
+				WaitPoll::Ready(delta_time)
+			}
+			Some(SpireYield::Seconds(seconds)) => {
+				// Comparing against a deadline (instead of repeatedly subtracting deltas) prevents
+				// floating-point errors from accumulating across many short waits.
+				let remaining = self.seconds_deadline - self.elapsed_seconds;
+				if remaining > 0.0 {
+					*seconds = remaining;
+					return WaitPoll::Pending;
+				}
+
+				self.last_yield = None;
+				WaitPoll::Ready(-remaining)
+			}
+			Some(SpireYield::SecondsUnscaled(remaining)) => {
+				let Some(unconsumed_delta) = count_down_unscaled(remaining, delta_time)
+				else { return WaitPoll::Pending };
+
+				self.last_yield = None;
+				WaitPoll::Ready(unconsumed_delta)
+			}
+			Some(dyn_yield @ (SpireYield::Dyn(_) | SpireYield::Inline(_))) => {
+				let Some(waiter) = dyn_yield.as_dyn_mut()
+				else { return WaitPoll::Ready(delta_time) };
+
+				match waiter.poll_wait(delta_time) {
+					WaitOutcome::Continue => WaitPoll::Pending,
+					WaitOutcome::Abort => {
+						self.last_yield = None;
+						WaitPoll::Abort
+					}
+					WaitOutcome::Resume => {
+						let unconsumed_delta = waiter.unconsumed_delta().unwrap_or(delta_time);
+						self.last_yield = None;
+						WaitPoll::Ready(unconsumed_delta)
+					}
+				}
+			}
+			None => WaitPoll::Ready(delta_time),
+		}
+	}
+
+	/// Starts waiting on `next_yield`, which the closure yielded with `delta_time` left in the current frame.
+	pub(crate) fn set_yield(&mut self, next_yield: SpireYield, delta_time: f64) {
+		let next_yield =
+			match next_yield {
+				SpireYield::Seconds(seconds) => {
+					// `delta_time` is the part of this poll's delta that wasn't consumed by previous yields.
+					self.seconds_deadline = self.elapsed_seconds - delta_time + seconds;
+					self.wait_total = seconds;
+					SpireYield::Seconds(seconds)
+				}
+				SpireYield::SecondsUnscaled(seconds) => {
+					// Unlike seconds, the remaining time can only be known on the next poll,
+					// since the engine's time scale may change in-between.
+					self.wait_total = seconds;
+					SpireYield::SecondsUnscaled(seconds)
+				}
+				SpireYield::Frames(frames) => {
+					// The current poll counts as the first frame of the wait.
+					self.frames_deadline = self.elapsed_frames.saturating_add(frames.max(0));
+					self.wait_total = frames.max(0) as f64;
+					SpireYield::Frames(frames.max(0))
+				}
+				dyn_yield @ (SpireYield::Dyn(_) | SpireYield::Inline(_)) => dyn_yield,
+			};
+
+		self.last_yield = Some(next_yield);
+	}
+}
@@ -0,0 +1,162 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use godot::classes::{Engine, SceneTree};
+use godot::prelude::*;
+
+use crate::prelude::*;
+use crate::yielding::SpireYield;
+
+/// A shared handle for controlling several coroutines as a unit.
+///
+/// The coroutines in a group may belong to different owner nodes. 
+/// Cloning the group creates another handle to the same group.
+///
+/// Coroutines that already finished are skipped by every operation, 
+/// and are removed from the group whenever it's queried or modified.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use godot::prelude::*;
+/// use gdext_coroutines::prelude::*;
+///
+/// fn showcase_group(node: Gd<Node>, other_node: Gd<Node>) {
+///     let group = CoroutineGroup::new(node.clone());
+///
+///     group.start(
+///         #[coroutine] || {
+///             yield seconds(2.0);
+///         });
+///
+///     group.add(other_node.start_coroutine(
+///         #[coroutine] || {
+///             yield frames(10);
+///         }));
+///
+///     node.start_coroutine(
+///         #[coroutine] move || {
+///             yield group.wait_all();
+///             godot_print!("Every coroutine in the group finished!");
+///         });
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CoroutineGroup {
+	owner: Gd<Node>,
+	coroutines: Rc<RefCell<Vec<Gd<SpireCoroutine>>>>,
+}
+
+impl CoroutineGroup {
+	/// Creates an empty group, whose [start](Self::start) spawns coroutines as children of `owner`.
+	pub fn new(owner: Gd<impl Inherits<Node>>) -> Self {
+		Self {
+			owner: owner.upcast(),
+			coroutines: Rc::new(RefCell::new(Vec::new())),
+		}
+	}
+
+	/// Spawns `f` with default settings as a child of the group's owner, then adds it to the group.
+	pub fn start<R>(
+		&self,
+		f: impl 'static + PollRoutine<Return = R>,
+	) -> Gd<SpireCoroutine>
+		where
+			R: 'static + ToGodot,
+	{
+		self.add(self.owner.start_coroutine(f))
+	}
+
+	/// Adds an already spawned coroutine to the group, the coroutine may belong to any owner.
+	pub fn add(&self, coroutine: Gd<SpireCoroutine>) -> Gd<SpireCoroutine> {
+		let mut coroutines = self.coroutines.borrow_mut();
+		coroutines.retain(|coroutine| !coroutine.is_finished());
+		coroutines.push(coroutine.clone());
+		coroutine
+	}
+
+	/// Returns the coroutines in the group that haven't finished yet.
+	pub fn coroutines(&self) -> Vec<Gd<SpireCoroutine>> {
+		let mut coroutines = self.coroutines.borrow_mut();
+		coroutines.retain(|coroutine| !coroutine.is_finished());
+		coroutines.clone()
+	}
+
+	/// How many coroutines in the group haven't finished yet.
+	pub fn len(&self) -> usize {
+		self.coroutines().len()
+	}
+
+	/// Returns true if every coroutine in the group finished (or if none were added).
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// See [SpireCoroutine::pause]
+	pub fn pause(&self) {
+		for mut coroutine in self.coroutines() {
+			coroutine.bind_mut().pause();
+		}
+	}
+
+	/// See [SpireCoroutine::resume]
+	pub fn resume(&self) {
+		for mut coroutine in self.coroutines() {
+			coroutine.bind_mut().resume();
+		}
+	}
+
+	/// See [SpireCoroutine::kill]
+	pub fn kill(&self) {
+		for mut coroutine in self.coroutines() {
+			coroutine.bind_mut().kill();
+		}
+
+		self.coroutines.borrow_mut().clear();
+	}
+
+	/// Coroutine resumes execution once every coroutine currently in the group has finished.
+	///
+	/// Coroutines added to the group after this call are not awaited.
+	pub fn wait_all(&self) -> SpireYield {
+		wait_all(self.coroutines())
+	}
+}
+
+/// Returns the coroutines tagged with `name` through [CoroutineBuilder::group] that haven't finished yet.
+///
+/// Only coroutines inside the scene tree are found, i.e. coroutines whose owner was removed from the tree are skipped.
+pub fn coroutines_in_group(name: impl Into<StringName>) -> Vec<Gd<SpireCoroutine>> {
+	let Some(mut tree) = Engine::singleton().get_main_loop().and_then(|main_loop| main_loop.try_cast::<SceneTree>().ok())
+	else {
+		return Vec::new();
+	};
+
+	tree.get_nodes_in_group(&name.into())
+	    .iter_shared()
+	    .filter_map(|node| node.try_cast::<SpireCoroutine>().ok())
+	    .filter(|coroutine| !coroutine.is_finished())
+	    .collect()
+}
+
+/// [Kills](SpireCoroutine::kill) every coroutine tagged with `name`, see [coroutines_in_group].
+pub fn kill_group(name: impl Into<StringName>) {
+	for mut coroutine in coroutines_in_group(name) {
+		coroutine.bind_mut().kill();
+	}
+}
+
+/// [Pauses](SpireCoroutine::pause) every coroutine tagged with `name`, see [coroutines_in_group].
+pub fn pause_group(name: impl Into<StringName>) {
+	for mut coroutine in coroutines_in_group(name) {
+		coroutine.bind_mut().pause();
+	}
+}
+
+/// [Resumes](SpireCoroutine::resume) every coroutine tagged with `name`, see [coroutines_in_group].
+pub fn resume_group(name: impl Into<StringName>) {
+	for mut coroutine in coroutines_in_group(name) {
+		coroutine.bind_mut().resume();
+	}
+}
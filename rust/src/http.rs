@@ -0,0 +1,48 @@
+use std::ops::Coroutine;
+
+use godot::classes::http_request;
+use godot::classes::HttpRequest;
+use godot::global::Error;
+use godot::prelude::*;
+
+use crate::prelude::*;
+use crate::yielding::SpireYield;
+
+/// The closure of [http_get](StartCoroutine::http_get)'s coroutine.
+pub(crate) fn http_get_routine(url: GString) -> impl 'static + Unpin + Coroutine<(), Yield = SpireYield, Return = Variant> {
+	#[coroutine] move || {
+		let Some(mut coroutine) = SpireCoroutine::current()
+		else { return Variant::nil() };
+
+		// As a child of the coroutine, the request is freed along with it, even if the coroutine is killed mid-request.
+		let mut request = HttpRequest::new_alloc();
+		coroutine.add_child(&request);
+
+		let completed = SignalAwaiter::new(request.clone(), "request_completed");
+		let error = request.request(&url);
+
+		if error != Error::OK {
+			godot_error!("`http_get` failed to start a request to \"{url}\": {error:?}");
+			request.queue_free();
+			return Variant::nil();
+		}
+
+		yield completed.yield_wait();
+		request.queue_free();
+
+		// request_completed(result: int, response_code: int, headers: PackedStringArray, body: PackedByteArray)
+		let args = completed.args().unwrap_or_default();
+		let result = args.first().and_then(|var| var.try_to::<i64>().ok());
+
+		if result != Some(http_request::Result::SUCCESS.ord() as i64) {
+			godot_error!("`http_get` request to \"{url}\" failed, result: {result:?}");
+			return Variant::nil();
+		}
+
+		let response_code = args.get(1).cloned().unwrap_or_default();
+		yield yield_value(response_code);
+
+		let body = args.get(3).and_then(|var| var.try_to::<PackedByteArray>().ok()).unwrap_or_default();
+		body.get_string_from_utf8().to_variant()
+	}
+}
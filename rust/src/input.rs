@@ -0,0 +1,107 @@
+use godot::classes::Input;
+use godot::prelude::*;
+
+use crate::prelude::*;
+use crate::yielding::SpireYield;
+
+/// Coroutine resumes execution once the input `action` has been held down continuously for `hold_secs`.
+///
+/// Releasing the action before `hold_secs` have passed resets the timer, the coroutine keeps waiting for the next long press.
+/// If the action is already held when the coroutine starts waiting, the hold is counted from that moment.
+///
+/// The hold time is accumulated from the poll's delta time, see [wait_until_stable].
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_for_long_press(node: Gd<Node>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                godot_print!("Hold to skip the cutscene...");
+///                yield wait_for_long_press("ui_accept", 1.5);
+///                godot_print!("Skipping!");
+///           });
+/// }
+/// ```
+pub fn wait_for_long_press(action: impl Into<StringName>, hold_secs: f64) -> SpireYield {
+	let action = action.into();
+	let input = Input::singleton();
+
+	wait_until_stable(hold_secs, move || input.is_action_pressed(&action))
+}
+
+/// Coroutine resumes execution once the input `action` is pressed twice, with at most `window_secs` between both presses.
+///
+/// Only presses that happen after the coroutine starts waiting are counted, an action that is already held down counts once it's released and pressed again.
+/// A press that comes too late starts a new window, as the first press of the next attempt.
+///
+/// The time between presses is accumulated from the poll's delta time (see [seconds] for how delta time depends on [PollMode]),
+/// and presses are detected by polling, so a press and release that both happen between two polls are missed.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_for_double_press(node: Gd<Node>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield wait_for_double_press("dash", 0.3);
+///                godot_print!("Dashing!");
+///           });
+/// }
+/// ```
+pub fn wait_for_double_press(action: impl Into<StringName>, window_secs: f64) -> SpireYield {
+	SpireYield::inline(DoublePress {
+		action: action.into(),
+		window: window_secs,
+		was_pressed: None,
+		since_first_press: None,
+	})
+}
+
+struct DoublePress {
+	action: StringName,
+	window: f64,
+	/// The action's state on the previous poll, unset before the first poll.
+	was_pressed: Option<bool>,
+	/// Seconds passed since the first press of the current attempt, if there's one.
+	since_first_press: Option<f64>,
+}
+
+impl KeepWaiting for DoublePress {
+	fn keep_waiting(&mut self, delta_time: f64) -> bool {
+		let pressed = Input::singleton().is_action_pressed(&self.action);
+		let just_pressed = pressed && self.was_pressed == Some(false);
+		self.was_pressed = Some(pressed);
+
+		if let Some(elapsed) = &mut self.since_first_press {
+			*elapsed += delta_time;
+		}
+
+		if !just_pressed {
+			return true;
+		}
+
+		match self.since_first_press {
+			Some(elapsed) if elapsed <= self.window => false,
+			_ => {
+				self.since_first_press = Some(0.0);
+				true
+			}
+		}
+	}
+
+	fn describe(&self) -> Option<String> {
+		match self.since_first_press {
+			Some(_) => Some(format!("waiting on the second press of action '{}'", self.action)),
+			None => Some(format!("waiting on a double press of action '{}'", self.action)),
+		}
+	}
+}
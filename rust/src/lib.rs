@@ -1,16 +1,18 @@
 //! WARNING: If you're using the latest github version of gdext, use this crate's latest github version too,
 //! it won't compile otherwise.
 
-#![feature(coroutines)]
-#![feature(coroutine_trait)]
-#![feature(stmt_expr_attributes)]
-#![feature(unboxed_closures)]
-#![cfg_attr(feature = "async", feature(async_fn_traits))]
+#![cfg_attr(not(feature = "stable"), feature(coroutines))]
+#![cfg_attr(not(feature = "stable"), feature(coroutine_trait))]
+#![cfg_attr(not(feature = "stable"), feature(stmt_expr_attributes))]
+#![cfg_attr(not(feature = "stable"), feature(unboxed_closures))]
+#![cfg_attr(all(feature = "async", not(feature = "stable")), feature(async_fn_traits))]
 
 #![allow(clippy::needless_return)]
 #![allow(clippy::useless_conversion)]
 #![allow(unused_doc_comments)]
 #![allow(private_bounds)]
+#![allow(clippy::result_large_err)]
+#![allow(unused_features)]
 
 
 #![doc = include_str!("../../README.md")]
@@ -18,43 +20,211 @@
 
 use godot::builtin::{Callable, Variant};
 
+#[cfg(not(feature = "stable"))]
+mod macros;
 mod coroutine;
 mod yielding;
 mod builder;
+mod driver;
+mod routine;
 mod start_coroutine;
+#[cfg(not(feature = "stable"))]
+mod routines;
+mod signals;
+#[cfg(not(feature = "stable"))]
+mod http;
+mod input;
+mod pool;
+mod settings;
+mod group;
+mod batch;
+mod virtual_clock;
+mod yield_record;
+mod stats;
 mod pinky_promise;
+mod state_machine;
 
 #[cfg(feature = "async")]
 mod start_async_task;
 
+#[cfg(feature = "async")]
+mod coroutine_future;
+
 pub(crate) enum OnFinishCall {
 	Closure(Box<dyn FnOnce(Variant)>),
+	/// Kept after being invoked, see [CoroutineBuilder::on_each_finish](prelude::CoroutineBuilder::on_each_finish).
+	Repeating(Box<dyn FnMut(Variant)>),
 	Callable(Callable),
 }
 
+/// Rebuilds a coroutine's closure after it panics, see [CoroutineBuilder::restart_on_panic](prelude::CoroutineBuilder::restart_on_panic).
+///
+/// The closure is rebuilt with the coroutine's restart factory, the same one used by [SpireCoroutine::restart](prelude::SpireCoroutine::restart).
+pub(crate) struct PanicRestart {
+	pub restarts_left: u32,
+}
+
+/// Checks whether a [Variant] can be converted into the coroutine's return type.
+///
+/// Only used in debug builds, by [SpireCoroutine::finish_with](prelude::SpireCoroutine::finish_with).
+#[derive(Clone, Copy)]
+#[cfg_attr(not(debug_assertions), allow(dead_code))]
+pub(crate) struct ResultCheck {
+	pub type_name: &'static str,
+	pub converts: fn(&Variant) -> bool,
+}
+
+impl ResultCheck {
+	pub fn of<R: godot::meta::FromGodot>() -> Self {
+		Self {
+			type_name: std::any::type_name::<R>(),
+			converts: |var| var.try_to::<R>().is_ok(),
+		}
+	}
+}
+
+/// Extracts the message of a caught panic, if it has one.
+pub(crate) fn panic_reason(err: &Box<dyn std::any::Any + Send>) -> String {
+	if let Some(str) = err.downcast_ref::<&str>() {
+		str.to_string()
+	} else if let Some(string) = err.downcast_ref::<String>() {
+		string.clone()
+	} else {
+		format!("{err:?}")
+	}
+}
+
 pub mod prelude {
 	pub use crate::coroutine::{
 		SpireCoroutine,
 		SIGNAL_FINISHED,
+		SIGNAL_WAITING,
+		SIGNAL_VALUE,
 		IsRunning,
 		IsFinished,
 		IsPaused,
 		PollMode,
+		FinishReason,
+		block_until_finished,
+		STUCK_THRESHOLD_DEFAULT,
+	};
+
+	pub use crate::driver::CoroutineDriver;
+
+	pub use crate::routine::{
+		PollRoutine,
+		RoutineState,
 	};
 
 	pub use crate::yielding::{
 		seconds,
+		real_seconds,
+		seconds_unscaled,
+		physics_seconds,
+		random_seconds,
+		random_frames,
+		random_seconds_with,
+		random_frames_with,
 		frames,
+		WaitStep,
+		frame_steps,
+		second_steps,
+		wait_sequence,
 		wait_while,
 		wait_until,
+		wait_all,
+		wait_any,
+		wait_until_freed,
+		wait_for_node_ready,
+		wait_for_change,
+		wait_for_some,
+		wait_until_stable,
+		wait_for_next_process_phase,
+		wait_for_next_physics_phase,
+		reparent_to,
+		yield_value,
 		KeepWaiting,
+		WaitCondition,
+		WaitOutcome,
 		WaitUntilFinished,
 		SpireYield as Yield,
 	};
 	
-	pub use crate::start_coroutine::StartCoroutine;
-	pub use crate::builder::CoroutineBuilder;
+	#[cfg(not(feature = "stable"))]
+	pub use crate::spire;
+
+	#[cfg(not(feature = "stable"))]
+	pub use crate::routines::budgeted_work;
+
+	pub use crate::settings::CoroutineSettings;
+
+	pub use crate::signals::{
+		SignalAwaiter,
+		join_results,
+		wait_for_signal,
+		wait_for_resized,
+		wait_for_viewport_size_changed,
+		wait_for_visibility_changed,
+		wait_for_tree_changed,
+		wait_for_any_signal,
+		wait_for_all_signals,
+		SignalArgs,
+		FiredSignal,
+		FiredSignals,
+	};
+
+	pub use crate::input::{
+		wait_for_long_press,
+		wait_for_double_press,
+	};
+
+	pub use crate::start_coroutine::{
+		StartCoroutine,
+		coroutine_on,
+		start_coroutine_on,
+		start_coroutine_detached_forget,
+	};
+	pub use crate::pool::CoroutinePool;
+	pub use crate::group::{
+		CoroutineGroup,
+		coroutines_in_group,
+		kill_group,
+		pause_group,
+		resume_group,
+	};
+
+	pub use crate::virtual_clock::VirtualClock;
+
+	pub use crate::batch::CoroutineBatch;
+
+	pub use crate::yield_record::{
+		YieldRecord,
+		YieldKind,
+	};
+
+	#[cfg(feature = "stats")]
+	pub use crate::stats::coroutine_stats;
+
+	pub use crate::builder::{
+		CoroutineBuilder,
+		CoroutineStartMode,
+		set_default_process_priority,
+		set_default_physics_priority,
+	};
 	
 	#[cfg(feature = "async")]
-	pub use crate::start_async_task::StartAsyncTask;
+	pub use crate::start_async_task::{
+		StartAsyncTask,
+		async_task_on,
+		start_async_task_on,
+	};
+
+	#[cfg(feature = "async")]
+	pub use crate::coroutine_future::CoroutineFuture;
+
+	pub use crate::state_machine::{
+		state_machine,
+		Step,
+		StateMachineCoroutine,
+	};
 }
\ No newline at end of file
@@ -0,0 +1,71 @@
+/// Creates a coroutine closure, taking care of the `#[coroutine]` attribute and the `move` capture.
+///
+/// `spire! { .. }` expands to `#[coroutine] move || { .. }`, which can then be passed to
+/// [start_coroutine](crate::prelude::StartCoroutine::start_coroutine) or [coroutine](crate::prelude::StartCoroutine::coroutine).
+///
+/// Forgetting `#[coroutine]` is the most common mistake when writing coroutines, and the compiler errors it produces
+/// don't point at the missing attribute.
+///
+/// The macro also has two forms that spawn the coroutine right away, both return the spawned `Gd<SpireCoroutine>`:
+/// - `spire!(node, { .. })` expands to `node.start_coroutine(#[coroutine] move || { .. })`.
+/// - `spire!(node => setting: value, ..; { .. })` expands to `node.coroutine(#[coroutine] move || { .. }).setting(value)..spawn()`,
+///   where each `setting` is a method of [CoroutineBuilder](crate::prelude::CoroutineBuilder).
+///   `process_mode` also accepts a bare [ProcessMode](godot::classes::node::ProcessMode) variant, like `process_mode: ALWAYS`.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_spire(node: Gd<Node>) {
+///     let message = "2 seconds passed!";
+///
+///     node.start_coroutine(spire! {
+///         yield seconds(2.0);
+///         godot_print!("{message}");
+///     });
+///
+///     let _coroutine = spire!(node, {
+///         yield seconds(1.0);
+///         42
+///     });
+///
+///     let _paused_coroutine = spire!(node => auto_start: false, process_mode: ALWAYS, poll_mode: PollMode::Physics; {
+///         yield frames(5);
+///         godot_print!("Runs even while the tree is paused");
+///     });
+/// }
+/// ```
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(not(feature = "stable"))))]
+macro_rules! spire {
+	(@settings $builder:expr; ) => {
+		$builder
+	};
+	(@settings $builder:expr; process_mode: $mode:ident $(, $($rest:tt)*)?) => {
+		$crate::spire!(@settings $builder.process_mode(::godot::classes::node::ProcessMode::$mode); $($($rest)*)?)
+	};
+	(@settings $builder:expr; $setting:ident: $value:expr $(, $($rest:tt)*)?) => {
+		$crate::spire!(@settings $builder.$setting($value); $($($rest)*)?)
+	};
+	(@split $node:expr; [$($settings:tt)*]; ; { $($body:tt)* }) => {
+		$crate::spire!(
+			@settings $crate::prelude::StartCoroutine::coroutine(&$node, #[coroutine] move || { $($body)* });
+			$($settings)*
+		).spawn()
+	};
+	(@split $node:expr; [$($settings:tt)*]; $next:tt $($rest:tt)*) => {
+		$crate::spire!(@split $node; [$($settings)* $next]; $($rest)*)
+	};
+	($node:expr => $($rest:tt)*) => {
+		$crate::spire!(@split $node; []; $($rest)*)
+	};
+	($node:expr, { $($body:tt)* }) => {
+		$crate::prelude::StartCoroutine::start_coroutine(&$node, #[coroutine] move || { $($body)* })
+	};
+	($($body:tt)*) => {
+		#[coroutine] move || { $($body)* }
+	};
+}
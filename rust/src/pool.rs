@@ -0,0 +1,113 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use godot::prelude::*;
+
+use crate::prelude::*;
+
+/// An opt-in pool of [SpireCoroutine] nodes, which reduces the cost of spawning many short-lived coroutines.
+///
+/// Coroutines spawned with [CoroutineBuilder::pool] are not freed when they end, instead they are removed from the tree,
+/// reset, and kept in the pool until a new coroutine is spawned with the same pool.
+///
+/// # Handles of pooled coroutines
+///
+/// Since a pooled node may be reused by a different coroutine, handles (`Gd<SpireCoroutine>`) to pooled coroutines stay valid
+/// after the coroutine ends. Keep in mind that:
+/// - [wait_until_finished](WaitUntilFinished::wait_until_finished) correctly resumes when the awaited coroutine ends,
+///   even if its node gets reused.
+/// - [is_finished](IsFinished::is_finished) and other queries on an old handle describe whatever coroutine currently occupies the node,
+///   use [SpireCoroutine::get_generation] to detect reuse.
+/// - Signal connections to [finished](SIGNAL_FINISHED) and [waiting](SIGNAL_WAITING) are removed when the node is recycled.
+///
+/// Idle nodes are freed when the last clone of the pool is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use godot::prelude::*;
+/// use gdext_coroutines::prelude::*;
+///
+/// fn showcase_pool(node: Gd<Node2D>, pool: &CoroutinePool) {
+///     for _ in 0..100 {
+///         node.coroutine(
+///             #[coroutine] || {
+///                 yield frames(1);
+///             })
+///             .pool(pool)
+///             .spawn();
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CoroutinePool {
+	inner: Rc<RefCell<PoolInner>>,
+}
+
+struct PoolInner {
+	idle: Vec<Gd<SpireCoroutine>>,
+	capacity: usize,
+}
+
+impl CoroutinePool {
+	/// Creates a pool that keeps at most `capacity` idle nodes, extra nodes are freed as usual.
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			inner: Rc::new(RefCell::new(PoolInner {
+				idle: Vec::with_capacity(capacity),
+				capacity,
+			})),
+		}
+	}
+
+	/// How many nodes are waiting to be reused.
+	pub fn idle_count(&self) -> usize {
+		self.inner.borrow().idle.len()
+	}
+
+	/// Frees every idle node.
+	pub fn clear(&self) {
+		self.inner.borrow_mut().clear();
+	}
+
+	pub(crate) fn take_idle(&self) -> Option<Gd<SpireCoroutine>> {
+		let mut inner = self.inner.borrow_mut();
+
+		while let Some(coroutine) = inner.idle.pop() {
+			if coroutine.is_instance_valid() {
+				return Some(coroutine);
+			}
+		}
+
+		None
+	}
+
+	/// Returns `coroutine` to the pool, fails if the pool is full.
+	pub(crate) fn try_put_idle(&self, coroutine: Gd<SpireCoroutine>) -> Result<(), Gd<SpireCoroutine>> {
+		let mut inner = self.inner.borrow_mut();
+
+		if inner.idle.len() < inner.capacity {
+			inner.idle.push(coroutine);
+			Ok(())
+		} else {
+			Err(coroutine)
+		}
+	}
+}
+
+impl PoolInner {
+	fn clear(&mut self) {
+		for mut coroutine in self.idle.drain(..) {
+			if coroutine.is_instance_valid() {
+				coroutine.queue_free();
+			}
+		}
+	}
+}
+
+impl Drop for PoolInner {
+	fn drop(&mut self) {
+		self.clear();
+	}
+}
@@ -0,0 +1,42 @@
+#[cfg(not(feature = "stable"))]
+use std::ops::{Coroutine, CoroutineState};
+#[cfg(not(feature = "stable"))]
+use std::pin::Pin;
+
+use crate::yielding::SpireYield;
+
+/// What a [PollRoutine] did when resumed.
+pub enum RoutineState<R> {
+	/// The routine is suspended, waiting on the yield.
+	Yielded(SpireYield),
+	/// The routine finished with the result, it must not be resumed again.
+	Complete(R),
+}
+
+/// A coroutine that [SpireCoroutine](crate::prelude::SpireCoroutine) can drive, resumed once after each of its yields.
+///
+/// Implemented by [StateMachineCoroutine](crate::prelude::StateMachineCoroutine), and by every [Unpin] Rust coroutine
+/// that yields [Yield](crate::prelude::Yield) (unless the feature "stable" is enabled).
+///
+/// Since resuming doesn't need [Pin] nor any unstable trait, it can also be implemented manually on a stable toolchain.
+pub trait PollRoutine {
+	type Return;
+
+	/// Runs the routine until its next yield, or until it finishes.
+	fn resume(&mut self) -> RoutineState<Self::Return>;
+}
+
+#[cfg(not(feature = "stable"))]
+impl<C> PollRoutine for C
+	where
+		C: Unpin + Coroutine<(), Yield = SpireYield>,
+{
+	type Return = C::Return;
+
+	fn resume(&mut self) -> RoutineState<C::Return> {
+		match Coroutine::resume(Pin::new(self), ()) {
+			CoroutineState::Yielded(_yield) => RoutineState::Yielded(_yield),
+			CoroutineState::Complete(result) => RoutineState::Complete(result),
+		}
+	}
+}
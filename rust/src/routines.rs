@@ -0,0 +1,60 @@
+use std::ops::Coroutine;
+use std::time::{Duration, Instant};
+
+use crate::prelude::*;
+use crate::yielding::SpireYield;
+
+/// Creates a coroutine that spreads `f` across as many frames as needed, spending at most `budget_ms` on it per frame.
+///
+/// `f` should do a small chunk of work and return `true` if there's more work to do, or `false` once it's done.
+///
+/// `f` is called in a loop until it either returns `false` (which finishes the coroutine),
+/// or the frame's budget is exceeded (which makes the coroutine continue on the next frame).
+/// 
+/// `f` is always called at least once per frame, even if a single call exceeds the budget.
+///
+/// The budget is measured in real time, it is not affected by [Engine::time_scale](godot::classes::Engine::get_time_scale).
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_budgeted_work(node: Gd<Node>, mut pending_chunks: Vec<i64>) {
+///      node.start_coroutine(
+///           budgeted_work(2.0, move || {
+///                if let Some(chunk) = pending_chunks.pop() {
+///                     godot_print!("Processing chunk: {chunk}");
+///                }
+///
+///                !pending_chunks.is_empty()
+///           }));
+/// }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(not(feature = "stable"))))]
+pub fn budgeted_work(
+	budget_ms: f64,
+	mut f: impl 'static + FnMut() -> bool,
+) -> impl 'static + Unpin + Coroutine<(), Yield = SpireYield, Return = ()> {
+	let budget = Duration::from_secs_f64(budget_ms.max(0.0) / 1000.0);
+
+	#[coroutine] move || {
+		loop {
+			let frame_start = Instant::now();
+
+			loop {
+				if !f() {
+					return;
+				}
+
+				if frame_start.elapsed() >= budget {
+					break;
+				}
+			}
+
+			yield frames(1);
+		}
+	}
+}
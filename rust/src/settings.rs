@@ -0,0 +1,36 @@
+use godot::classes::node::ProcessMode;
+use godot::prelude::*;
+
+use crate::prelude::*;
+
+/// A [Resource] holding coroutine settings, which can be edited in the inspector and shared between scenes.
+///
+/// Apply it to a builder with [CoroutineBuilder::from_settings].
+#[derive(GodotClass)]
+#[class(init, base = Resource)]
+pub struct CoroutineSettings {
+	/// Whether the coroutine polls on process or physics frames, see [CoroutineBuilder::poll_mode].
+	#[export]
+	#[init(val = PollMode::Process)]
+	pub poll_mode: PollMode,
+	/// Godot [ProcessMode] which the coroutine should run in, see [CoroutineBuilder::process_mode].
+	///
+	/// Stored as the enum's ordinal, use [to_process_mode](Self::to_process_mode) to read it as a [ProcessMode].
+	#[export(enum = (Inherit = 0, Pausable = 1, WhenPaused = 2, Always = 3, Disabled = 4))]
+	pub process_mode: i32,
+	/// Seconds to wait before the coroutine's first step, see [CoroutineBuilder::start_delay].
+	#[export]
+	pub start_delay: f64,
+	/// Multiplier for the delta time the coroutine receives, see [CoroutineBuilder::time_scale].
+	#[export]
+	#[init(val = 1.0)]
+	pub time_scale: f64,
+	base: Base<Resource>,
+}
+
+impl CoroutineSettings {
+	/// Returns [process_mode](Self::process_mode) as a [ProcessMode], falling back to [ProcessMode::INHERIT] if it's out of range.
+	pub fn to_process_mode(&self) -> ProcessMode {
+		ProcessMode::try_from_ord(self.process_mode).unwrap_or(ProcessMode::INHERIT)
+	}
+}
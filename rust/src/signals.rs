@@ -0,0 +1,567 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use godot::classes::object::ConnectFlags;
+use godot::classes::{CanvasItem, Control, Viewport};
+use godot::obj::EngineEnum;
+use godot::prelude::*;
+
+use crate::prelude::*;
+use crate::yielding::{run_ended, SpireYield};
+
+/// Waits for a single emission of a Godot signal.
+///
+/// The connection is one-shot: after the first emission, the signal is automatically disconnected
+/// and any further emissions are ignored.
+///
+/// The awaiter can be cloned, all clones share the same connection.
+/// If the signal never fires, the connection is removed once every clone is dropped,
+/// which includes the clone held by a coroutine that was [killed](SpireCoroutine::kill) while waiting on it.
+///
+/// If `object` is freed before emitting the signal, coroutines waiting on the awaiter resume without any arguments.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::classes::Button;
+/// use godot::prelude::*;
+///
+/// fn showcase_signal_awaiter(node: Gd<Node>, button: Gd<Button>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                let pressed = SignalAwaiter::new(button, "pressed");
+///                yield pressed.yield_wait();
+///                godot_print!("Button was pressed! Resuming...");
+///           });
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SignalAwaiter {
+	connection: Rc<SignalConnection>,
+}
+
+struct SignalConnection {
+	object: Gd<Object>,
+	signal: StringName,
+	callable: Callable,
+	args: Rc<RefCell<Option<Vec<Variant>>>>,
+}
+
+impl SignalAwaiter {
+	/// Connects to `object`'s `signal`.
+	///
+	/// Logs an error if the connection fails (e.g. `object` doesn't have such signal),
+	/// in which case waiting on the awaiter resumes immediately.
+	pub fn new(object: Gd<impl Inherits<Object>>, signal: impl Into<StringName>) -> Self {
+		let mut object = object.upcast::<Object>();
+		let signal = signal.into();
+		let args = Rc::new(RefCell::new(None));
+
+		let callable = {
+			let args = args.clone();
+
+			Callable::from_local_fn(&GString::from(format!("await_{signal}")),
+				move |emitted_args| {
+					let mut args = args.borrow_mut();
+					if args.is_none() {
+						*args = Some(emitted_args.iter().map(|&arg| arg.clone()).collect());
+					}
+
+					Ok(Variant::nil())
+				})
+		};
+
+		let error =
+			object.connect_ex(&signal, &callable)
+			      .flags(ConnectFlags::ONE_SHOT.ord() as u32)
+			      .done();
+
+		if error != godot::global::Error::OK {
+			godot_error!("Failed to connect to signal `{signal}` of `{object}`: {error:?}");
+			*args.borrow_mut() = Some(Vec::new());
+		}
+
+		Self {
+			connection: Rc::new(SignalConnection {
+				object,
+				signal,
+				callable,
+				args,
+			}),
+		}
+	}
+
+	/// Connects to the signal represented by gdext's [Signal] type.
+	///
+	/// Returns `None` if `signal` doesn't point to an object, e.g. [Signal::invalid] or its object was freed.
+	pub fn from_signal(signal: &Signal) -> Option<Self> {
+		signal
+			.object()
+			.map(|object| Self::new(object, signal.name()))
+	}
+
+	/// Returns true if the signal was emitted.
+	pub fn has_fired(&self) -> bool {
+		self.connection.args.borrow().is_some()
+	}
+
+	/// The arguments the signal was emitted with, or `None` if it wasn't emitted yet.
+	pub fn args(&self) -> Option<Vec<Variant>> {
+		self.connection.args.borrow().clone()
+	}
+
+	/// Creates a yield that resumes the coroutine once the signal is emitted.
+	///
+	/// Resumes immediately if the signal was already emitted.
+	pub fn yield_wait(&self) -> SpireYield {
+		SpireYield::Dyn(Box::new(self.clone()))
+	}
+
+	/// Disconnects from the signal, if still connected.
+	///
+	/// Coroutines waiting on this awaiter will keep waiting until `object` is freed.
+	pub fn disconnect(&self) {
+		self.connection.disconnect();
+	}
+}
+
+/// Allows yielding gdext's [Signal] values directly, resuming once the signal is emitted.
+///
+/// Equivalent to waiting on [SignalAwaiter::from_signal], logs an error and resumes immediately if the signal doesn't point to an object.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::classes::Timer;
+/// use godot::prelude::*;
+///
+/// fn showcase_yield_signal(node: Gd<Node>, timer: Gd<Timer>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield Signal::from_object_signal(&timer, "timeout").into();
+///                godot_print!("Timer timed out! Resuming...");
+///           });
+/// }
+/// ```
+impl From<Signal> for SpireYield {
+	fn from(signal: Signal) -> Self {
+		match SignalAwaiter::from_signal(&signal) {
+			Some(awaiter) => awaiter.yield_wait(),
+			None => {
+				godot_error!("Cannot wait for signal `{}`: it doesn't point to a valid object.", signal.name());
+				frames(0)
+			}
+		}
+	}
+}
+
+impl SignalConnection {
+	fn disconnect(&self) {
+		let mut object = self.object.clone();
+
+		if object.is_instance_valid() && object.is_connected(&self.signal, &self.callable) {
+			object.disconnect(&self.signal, &self.callable);
+		}
+	}
+}
+
+impl Drop for SignalConnection {
+	fn drop(&mut self) {
+		self.disconnect();
+	}
+}
+
+impl KeepWaiting for SignalAwaiter {
+	fn keep_waiting(&mut self, _delta_time: f64) -> bool {
+		!self.has_fired() && self.connection.object.is_instance_valid()
+	}
+
+	fn describe(&self) -> Option<String> {
+		Some(format!("waiting on signal '{}' of {}", self.connection.signal, self.connection.object))
+	}
+}
+
+/// Coroutine resumes execution once `object` emits `signal`.
+///
+/// Shorthand for waiting on a new [SignalAwaiter]: the connection is one-shot, and it's removed if the coroutine is
+/// [killed](SpireCoroutine::kill) while waiting. Logs an error and resumes immediately if the connection fails.
+///
+/// Also returns a cell that will contain the arguments the signal was emitted with, like [SignalAwaiter::args].
+/// If the connection failed, the cell contains an empty list.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::classes::{Area2D, Button};
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_for_signal(node: Gd<Node>, button: Gd<Button>, area: Gd<Area2D>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield wait_for_signal(button, "pressed").0;
+///                godot_print!("Button was pressed! Resuming...");
+///
+///                let (wait, args) = wait_for_signal(area, "body_entered");
+///                yield wait;
+///                godot_print!("Body entered: {:?}", args.borrow());
+///           });
+/// }
+/// ```
+pub fn wait_for_signal(object: Gd<impl Inherits<Object>>, signal: impl Into<StringName>) -> (SpireYield, SignalArgs) {
+	let awaiter = SignalAwaiter::new(object, signal);
+	let args = awaiter.connection.args.clone();
+	(awaiter.yield_wait(), args)
+}
+
+/// Coroutine resumes execution once `control` emits [resized](Control::resized).
+///
+/// Shorthand for waiting on a [SignalAwaiter], the connection is removed once the signal fires or the yield is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::classes::Control;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_for_resized(node: Gd<Node>, panel: Gd<Control>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield wait_for_resized(panel.clone());
+///                godot_print!("Panel resized to {}! Resuming...", panel.get_size());
+///           });
+/// }
+/// ```
+pub fn wait_for_resized(control: Gd<impl Inherits<Control>>) -> SpireYield {
+	SignalAwaiter::new(control.upcast::<Control>(), "resized").yield_wait()
+}
+
+/// Coroutine resumes execution once `viewport` emits [size_changed](Viewport::size_changed).
+///
+/// Shorthand for waiting on a [SignalAwaiter], see [wait_for_resized].
+pub fn wait_for_viewport_size_changed(viewport: Gd<impl Inherits<Viewport>>) -> SpireYield {
+	SignalAwaiter::new(viewport.upcast::<Viewport>(), "size_changed").yield_wait()
+}
+
+/// Coroutine resumes execution once `canvas_item` emits [visibility_changed](CanvasItem::visibility_changed).
+///
+/// Shorthand for waiting on a [SignalAwaiter], see [wait_for_resized].
+pub fn wait_for_visibility_changed(canvas_item: Gd<impl Inherits<CanvasItem>>) -> SpireYield {
+	SignalAwaiter::new(canvas_item.upcast::<CanvasItem>(), "visibility_changed").yield_wait()
+}
+
+/// Coroutine resumes execution once the [SceneTree](godot::classes::SceneTree) `node` is in emits [tree_changed](godot::classes::SceneTree::tree_changed),
+/// which happens whenever a node is added, removed or moved anywhere in the tree.
+///
+/// Logs an error and resumes immediately if `node` is not inside a tree.
+///
+/// Shorthand for waiting on a [SignalAwaiter], see [wait_for_resized].
+pub fn wait_for_tree_changed(node: Gd<impl Inherits<Node>>) -> SpireYield {
+	let node = node.upcast::<Node>();
+
+	match node.get_tree() {
+		Some(tree) => SignalAwaiter::new(tree, "tree_changed").yield_wait(),
+		None => {
+			godot_error!("Cannot wait for `tree_changed`: `{node}` is not inside a scene tree.");
+			frames(0)
+		}
+	}
+}
+
+/// Arguments of the signal awaited by a [wait_for_signal] yield, `None` until it's emitted.
+pub type SignalArgs = Rc<RefCell<Option<Vec<Variant>>>>;
+
+/// Index and arguments of the signal that resumed a [wait_for_any_signal] yield.
+pub type FiredSignal = Rc<RefCell<Option<(usize, Vec<Variant>)>>>;
+
+/// Arguments of each signal awaited by a [wait_for_all_signals] yield, `None` for signals whose object was freed.
+pub type FiredSignals = Rc<RefCell<Vec<Option<Vec<Variant>>>>>;
+
+/// Coroutine resumes execution once any of `signals` is emitted.
+///
+/// Each element of `signals` is an object paired with the name of one of its signals.
+///
+/// Also returns a cell that will contain the index (in `signals`) of the first signal emitted, along with its arguments.
+/// Once a signal fires, all connections are removed, further emissions are ignored.
+/// Connections are also removed if the coroutine is [killed](SpireCoroutine::kill) while waiting.
+///
+/// Objects freed before emitting are ignored, if all of them are freed the coroutine resumes and the cell stays `None`.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::classes::Button;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_for_any_signal(node: Gd<Node>, ok: Gd<Button>, cancel: Gd<Button>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                let (wait, fired) = wait_for_any_signal([(ok, "pressed"), (cancel, "pressed")]);
+///                yield wait;
+///
+///                let fired = fired.borrow().clone();
+///                match fired {
+///                     Some((0, _)) => godot_print!("Ok was pressed!"),
+///                     Some(_) => godot_print!("Cancel was pressed!"),
+///                     None => godot_print!("Both buttons were freed."),
+///                }
+///           });
+/// }
+/// ```
+pub fn wait_for_any_signal<O, S>(
+	signals: impl IntoIterator<Item = (Gd<O>, S)>,
+) -> (SpireYield, FiredSignal)
+	where
+		O: Inherits<Object>,
+		S: Into<StringName>,
+{
+	let awaiters =
+		signals
+			.into_iter()
+			.map(|(object, signal)| SignalAwaiter::new(object, signal))
+			.collect();
+
+	let fired = Rc::new(RefCell::new(None));
+
+	let any = AnySignal {
+		awaiters,
+		fired: fired.clone(),
+	};
+
+	(SpireYield::Dyn(Box::new(any)), fired)
+}
+
+struct AnySignal {
+	awaiters: Vec<SignalAwaiter>,
+	fired: FiredSignal,
+}
+
+impl KeepWaiting for AnySignal {
+	fn keep_waiting(&mut self, _delta_time: f64) -> bool {
+		let first_fired =
+			self.awaiters
+			    .iter()
+			    .enumerate()
+			    .find_map(|(index, awaiter)| awaiter.args().map(|args| (index, args)));
+
+		if let Some(fired) = first_fired {
+			*self.fired.borrow_mut() = Some(fired);
+			self.awaiters.iter().for_each(SignalAwaiter::disconnect);
+			return false;
+		}
+
+		self.awaiters
+		    .iter()
+		    .any(|awaiter| awaiter.connection.object.is_instance_valid())
+	}
+
+	fn describe(&self) -> Option<String> {
+		let signals =
+			self.awaiters
+			    .iter()
+			    .map(|awaiter| format!("'{}'", awaiter.connection.signal))
+			    .collect::<Vec<_>>()
+			    .join(", ");
+
+		Some(format!("waiting on any of {} signals: {signals}", self.awaiters.len()))
+	}
+}
+
+/// Coroutine resumes execution once each of `signals` has been emitted at least once.
+///
+/// Each element of `signals` is an object paired with the name of one of its signals.
+///
+/// Also returns a list that will contain the arguments of each signal's first emission, in the same order as `signals`.
+/// Each connection is removed after its first emission, and all of them are removed if the coroutine is
+/// [killed](SpireCoroutine::kill) while waiting.
+///
+/// Signals whose object is freed before emitting count as done, their entry in the list stays `None`.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_for_all_signals(node: Gd<Node>, switches: Vec<Gd<Node>>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                let (wait, _) = wait_for_all_signals(switches.into_iter().map(|switch| (switch, "toggled")));
+///                yield wait;
+///                godot_print!("All switches were toggled! Opening the door...");
+///           });
+/// }
+/// ```
+pub fn wait_for_all_signals<O, S>(
+	signals: impl IntoIterator<Item = (Gd<O>, S)>,
+) -> (SpireYield, FiredSignals)
+	where
+		O: Inherits<Object>,
+		S: Into<StringName>,
+{
+	let awaiters =
+		signals
+			.into_iter()
+			.map(|(object, signal)| SignalAwaiter::new(object, signal))
+			.collect::<Vec<_>>();
+
+	let fired = Rc::new(RefCell::new(vec![None; awaiters.len()]));
+
+	let all = AllSignals {
+		awaiters,
+		fired: fired.clone(),
+	};
+
+	(SpireYield::Dyn(Box::new(all)), fired)
+}
+
+struct AllSignals {
+	awaiters: Vec<SignalAwaiter>,
+	fired: FiredSignals,
+}
+
+impl KeepWaiting for AllSignals {
+	fn keep_waiting(&mut self, delta_time: f64) -> bool {
+		self.poll_wait(delta_time) == WaitOutcome::Continue
+	}
+
+	fn poll_wait(&mut self, delta_time: f64) -> WaitOutcome {
+		let mut fired = self.fired.borrow_mut();
+		let mut outcome = WaitOutcome::Resume;
+
+		for (index, awaiter) in self.awaiters.iter_mut().enumerate() {
+			if let Some(args) = awaiter.args() {
+				fired[index] = Some(args);
+				continue;
+			}
+
+			match awaiter.poll_wait(delta_time) {
+				WaitOutcome::Abort => return WaitOutcome::Abort,
+				WaitOutcome::Continue => outcome = WaitOutcome::Continue,
+				WaitOutcome::Resume => {}
+			}
+		}
+
+		outcome
+	}
+
+	fn describe(&self) -> Option<String> {
+		let pending =
+			self.awaiters
+			    .iter()
+			    .filter(|awaiter| !awaiter.has_fired() && awaiter.connection.object.is_instance_valid())
+			    .count();
+
+		Some(format!("waiting on {pending} of {} signals", self.awaiters.len()))
+	}
+}
+
+/// Coroutine resumes execution once every coroutine in `coroutines` has finished (or was freed).
+///
+/// Also returns a list that will contain the result of each coroutine, in the same order as `coroutines`.
+/// The list is only complete after the yield resumes, coroutines that ended without emitting [finished](SIGNAL_FINISHED)
+/// (e.g. they were [killed](SpireCoroutine::kill)) have `nil` as their result.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_join_results(node: Gd<Node>, first: Gd<SpireCoroutine>, second: Gd<SpireCoroutine>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                let (wait, results) = join_results([first, second]);
+///                yield wait;
+///                godot_print!("Both coroutines finished! Results: {:?}", results.borrow());
+///           });
+/// }
+/// ```
+pub fn join_results(
+	coroutines: impl IntoIterator<Item = Gd<SpireCoroutine>>,
+) -> (SpireYield, Rc<RefCell<Vec<Variant>>>) {
+	let awaiters =
+		coroutines
+			.into_iter()
+			.map(|coroutine| {
+				// Freed coroutines can't be cloned nor connected to, their slot is left as `nil`.
+				if !coroutine.is_instance_valid() {
+					return None;
+				}
+
+				let generation = coroutine.bind().generation;
+				let awaiter = SignalAwaiter::new(coroutine.clone(), SIGNAL_FINISHED);
+				Some((coroutine, generation, awaiter))
+			})
+			.collect::<Vec<_>>();
+
+	let results = Rc::new(RefCell::new(vec![Variant::nil(); awaiters.len()]));
+
+	let join = JoinResults {
+		awaiters,
+		results: results.clone(),
+	};
+
+	(SpireYield::Dyn(Box::new(join)), results)
+}
+
+struct JoinResults {
+	/// Each coroutine along with the [generation](SpireCoroutine::get_generation) of the run being awaited,
+	/// `None` if the coroutine was already freed when [join_results] was called.
+	awaiters: Vec<Option<(Gd<SpireCoroutine>, u64, SignalAwaiter)>>,
+	results: Rc<RefCell<Vec<Variant>>>,
+}
+
+impl KeepWaiting for JoinResults {
+	fn keep_waiting(&mut self, delta_time: f64) -> bool {
+		self.poll_wait(delta_time) == WaitOutcome::Continue
+	}
+
+	fn poll_wait(&mut self, delta_time: f64) -> WaitOutcome {
+		let mut results = self.results.borrow_mut();
+		let mut outcome = WaitOutcome::Resume;
+
+		for (index, awaiter) in self.awaiters.iter_mut().enumerate() {
+			let Some((coroutine, generation, awaiter)) = awaiter
+			else { continue };
+
+			if let Some(result) = awaiter.args().and_then(|args| args.into_iter().next()) {
+				results[index] = result;
+				continue;
+			}
+
+			// The awaiter resumes once its coroutine's node is freed, but a run can also end while the node lives on.
+			if awaiter.poll_wait(delta_time) == WaitOutcome::Abort {
+				return WaitOutcome::Abort;
+			}
+
+			if !run_ended(coroutine, *generation) {
+				outcome = WaitOutcome::Continue;
+			}
+		}
+
+		outcome
+	}
+
+	fn describe(&self) -> Option<String> {
+		let pending =
+			self.awaiters
+			    .iter()
+			    .flatten()
+			    .filter(|(coroutine, generation, awaiter)| !awaiter.has_fired() && !run_ended(coroutine, *generation))
+			    .count();
+
+		Some(format!("waiting on {pending} of {} coroutines", self.awaiters.len()))
+	}
+}
@@ -30,6 +30,17 @@ pub trait StartAsyncTask {
 		self.async_task(f).spawn()
 	}
 	
+	/// Starts a new async_task with default settings, see [async_task_eager](Self::async_task_eager).
+	fn start_async_task_eager<R>(
+		&self,
+		f: impl Future<Output = R> + Send + 'static,
+	) -> Gd<SpireCoroutine>
+		where
+			R: 'static + ToGodot + Send,
+	{
+		self.async_task_eager(f).spawn()
+	}
+
 	/// Just like [start_async_task], but does not enforce the `Send` bound.
 	/// 
 	/// # Safety
@@ -46,6 +57,11 @@ pub trait StartAsyncTask {
 	///
 	/// The coroutine does not actually `spawn` until you call [CoroutineBuilder::spawn].
 	///
+	/// The future's output is the coroutine's result, like with regular coroutines it's passed to the 
+	/// [on_finish](CoroutineBuilder::on_finish) callbacks and emitted with [finished](SIGNAL_FINISHED).
+	///
+	/// If `f` panics, the panic is caught and logged, then the coroutine finishes with `nil`.
+	///
 	/// # Example
 	///
 	/// ```no_run
@@ -73,6 +89,43 @@ pub trait StartAsyncTask {
 		where
 			R: 'static + ToGodot + Send;
 	
+	/// Just like [async_task], but `f` is polled once on the calling thread before being sent to the background.
+	///
+	/// If `f` is already finished after that poll (e.g. it returns a cached value), no background task is spawned,
+	/// and the coroutine completes synchronously in [spawn](CoroutineBuilder::spawn),
+	/// as if spawned with [CoroutineStartMode::Immediate].
+	/// The returned coroutine is then already [finished](SpireCoroutine::is_finished),
+	/// so only callbacks registered through the builder (e.g. [on_finish](CoroutineBuilder::on_finish)) observe its result.
+	/// Nothing is polled until then, and spawning it paused (e.g. with [auto_start(false)](CoroutineBuilder::auto_start))
+	/// or with [CoroutineStartMode::Deferred] defers that poll to the coroutine's first step.
+	///
+	/// Since the first poll happens on the calling thread, avoid this for futures that do heavy work before their first `await`.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_async_task_eager(node: Gd<Node2D>, cached: Option<String>) {
+	///     node.async_task_eager(
+	///         async move {
+	///              match cached {
+	///                  Some(text) => text,
+	///                  None => smol::fs::read_to_string("hello.txt").await.unwrap(),
+	///              }
+	///         })
+	///         .spawn();
+	/// }
+	/// ```
+	fn async_task_eager<R>(
+		&self,
+		f: impl Future<Output = R> + Send + 'static,
+	) -> CoroutineBuilder<R>
+		where
+			R: 'static + ToGodot + Send;
+
 	/// Just like [async_task], but does not enforce the `Send` bound.
 	/// 
 	/// # Safety
@@ -98,6 +151,16 @@ impl<TSelf> StartAsyncTask for Gd<TSelf>
 		CoroutineBuilder::new_async_task(self.clone().upcast(), f)
 	}
 
+	fn async_task_eager<R>(
+		&self,
+		f: impl Future<Output = R> + Send + 'static,
+	) -> CoroutineBuilder<R>
+		where
+			R: 'static + ToGodot + Send,
+	{
+		CoroutineBuilder::new_async_task_eager(self.clone().upcast(), f)
+	}
+
 	unsafe fn async_task_unchecked<R: 'static + ToGodot>(
 		&self,
 		f: impl Future<Output = R> + Unpin + 'static
@@ -121,6 +184,17 @@ impl<T> StartAsyncTask for &T
 		CoroutineBuilder::new_async_task(base.upcast(), f)
 	}
 
+	fn async_task_eager<R>(
+		&self,
+		f: impl Future<Output = R> + Send + 'static,
+	) -> CoroutineBuilder<R>
+		where
+			R: 'static + ToGodot + Send,
+	{
+		let base = self.base_field().to_gd();
+		CoroutineBuilder::new_async_task_eager(base.upcast(), f)
+	}
+
 	unsafe fn async_task_unchecked<R: 'static + ToGodot>(
 		&self,
 		f: impl Future<Output = R> + Unpin + 'static
@@ -145,6 +219,17 @@ impl<T> StartAsyncTask for &mut T
 		CoroutineBuilder::new_async_task(base.upcast(), f)
 	}
 
+	fn async_task_eager<R>(
+		&self,
+		f: impl Future<Output = R> + Send + 'static,
+	) -> CoroutineBuilder<R>
+		where
+			R: 'static + ToGodot + Send,
+	{
+		let base = self.base_field().to_gd();
+		CoroutineBuilder::new_async_task_eager(base.upcast(), f)
+	}
+
 	unsafe fn async_task_unchecked<R: 'static + ToGodot>(
 		&self,
 		f: impl Future<Output = R> + Unpin + 'static
@@ -152,4 +237,29 @@ impl<T> StartAsyncTask for &mut T
 		let base = self.base_field().to_gd();
 		CoroutineBuilder::new_async_task_unchecked(base.upcast(), f)
 	}
-}
\ No newline at end of file
+}
+
+/// Creates an async task builder whose coroutine will be a child of `parent`, 
+/// for callers that don't inherit [Node] and therefore can't use [StartAsyncTask].
+///
+/// See [coroutine_on] for an example.
+pub fn async_task_on<R>(
+	parent: Gd<impl Inherits<Node>>,
+	f: impl Future<Output = R> + Send + 'static,
+) -> CoroutineBuilder<R>
+	where
+		R: 'static + ToGodot + Send,
+{
+	CoroutineBuilder::new_async_task(parent.upcast(), f)
+}
+
+/// Spawns and starts a new async task with default settings as a child of `parent`, see [async_task_on].
+pub fn start_async_task_on<R>(
+	parent: Gd<impl Inherits<Node>>,
+	f: impl Future<Output = R> + Send + 'static,
+) -> Gd<SpireCoroutine>
+	where
+		R: 'static + ToGodot + Send,
+{
+	async_task_on(parent, f).spawn()
+}
@@ -1,9 +1,24 @@
+#[cfg(not(feature = "stable"))]
 use std::ops::Coroutine;
+use godot::classes::{Engine, SceneTree};
 use godot::obj::WithBaseField;
 use godot::prelude::*;
 use crate::prelude::*;
+#[cfg(not(feature = "stable"))]
 use crate::yielding::SpireYield;
 
+/// Implemented for:
+/// - `Gd<T>`, where `T` inherits [Node].
+/// - `&T` and `&mut T`, where `T`'s base inherits [Node].
+///
+/// Which means you can call `self.start_coroutine(..)` from inside the methods of your own classes.
+///
+/// Types that don't inherit [Node] (e.g. a `RefCounted` controller) can use [coroutine_on] and [start_coroutine_on] instead, 
+/// which take the node the coroutine will be a child of.
+#[diagnostic::on_unimplemented(
+	message = "`{Self}` cannot start coroutines",
+	note = "coroutines can only be started from `Gd<T>` where `T` inherits `Node`, or from `&T`/`&mut T` where `T`'s base inherits `Node`",
+)]
 pub trait StartCoroutine {
 	/// Spawns and starts a new coroutine with default settings.
 	///
@@ -22,12 +37,19 @@ pub trait StartCoroutine {
 	///         });
 	/// }
 	/// ```
+	///
+	/// # First step
+	/// The coroutine's first step never runs during this call, it runs on the coroutine's first
+	/// [process](INode::process)/[physics_process](INode::physics_process).
+	///
+	/// This also holds when called from the owner's [ready](INode::ready): the coroutine first runs on the next processed frame,
+	/// after every node in the scene is ready. Use [CoroutineBuilder::start_mode] to run the first step synchronously.
 	/// 
 	/// # On Panics
 	/// If `f` panics, the SpireCoroutine will automatically self-destruct and the closure will be leaked
 	fn start_coroutine<R>(
 		&self,
-		f: impl 'static + Unpin + Coroutine<(), Yield = SpireYield, Return = R>,
+		f: impl 'static + PollRoutine<Return = R>,
 	) -> Gd<SpireCoroutine>
 		where
 			R: 'static + ToGodot,
@@ -64,10 +86,145 @@ pub trait StartCoroutine {
 	/// If `f` panics, the SpireCoroutine will automatically self-destruct and the closure will be leaked
 	fn coroutine<R>(
 		&self,
-		f: impl 'static + Unpin + Coroutine<(), Yield = SpireYield, Return = R>,
+		f: impl 'static + PollRoutine<Return = R>,
 	) -> CoroutineBuilder<R>
 		where
 			R: 'static + ToGodot;
+
+	/// Like [coroutine](Self::coroutine), but accepts coroutines that aren't [Unpin].
+	///
+	/// `f` is pinned on the heap, which allows `static` coroutines: the ones that hold references to their own locals across yields.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_coroutine_pinned(node: Gd<Node2D>) {
+	///     node.coroutine_pinned(
+	///         #[coroutine] static || {
+	///             let names = vec!["Alice", "Bob"];
+	///             let first = &names[0];
+	///             yield seconds(2.0);
+	///             godot_print!("Hello, {first}!");
+	///         })
+	///         .spawn();
+	/// }
+	/// ```
+	#[cfg(not(feature = "stable"))]
+	#[cfg_attr(docsrs, doc(cfg(not(feature = "stable"))))]
+	fn coroutine_pinned<R>(
+		&self,
+		f: impl 'static + Coroutine<(), Yield = SpireYield, Return = R>,
+	) -> CoroutineBuilder<R>
+		where
+			R: 'static + ToGodot,
+	{
+		self.coroutine(Box::pin(f))
+	}
+
+	/// Spawns and starts a new coroutine with default settings, see [coroutine_pinned](Self::coroutine_pinned).
+	#[cfg(not(feature = "stable"))]
+	#[cfg_attr(docsrs, doc(cfg(not(feature = "stable"))))]
+	fn start_coroutine_pinned<R>(
+		&self,
+		f: impl 'static + Coroutine<(), Yield = SpireYield, Return = R>,
+	) -> Gd<SpireCoroutine>
+		where
+			R: 'static + ToGodot,
+	{
+		self.coroutine_pinned(f).spawn()
+	}
+
+	/// Spawns one coroutine per item, created by `make`, all hosted by a single [SpireCoroutine] node.
+	///
+	/// Meant for large populations of similar, short-lived coroutines (e.g. one per projectile), 
+	/// where spawning a node for each of them would dominate the cost.
+	///
+	/// Members wait on yields like regular coroutines do, but they share the host's settings (poll mode, pausing, etc.),
+	/// and can only be controlled as a unit through the returned [CoroutineBatch].
+	/// A member that panics or aborts finishes with `nil`, without affecting the others.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_spawn_many(node: Gd<Node2D>, projectiles: Vec<Gd<Node2D>>) {
+	///     let batch =
+	///         node.spawn_many(projectiles, |mut projectile| {
+	///             #[coroutine] move || {
+	///                 for _ in 0..60 {
+	///                     let position = projectile.get_position();
+	///                     projectile.set_position(position + Vector2::new(4.0, 0.0));
+	///                     yield frames(1);
+	///                 }
+	///
+	///                 projectile.queue_free();
+	///             }
+	///         });
+	///
+	///     node.start_coroutine(
+	///         #[coroutine] move || {
+	///             yield batch.wait_all();
+	///             godot_print!("Every projectile expired!");
+	///         });
+	/// }
+	/// ```
+	fn spawn_many<T, C, R>(
+		&self,
+		items: impl IntoIterator<Item = T>,
+		make: impl FnMut(T) -> C,
+	) -> CoroutineBatch
+		where
+			C: 'static + PollRoutine<Return = R>,
+			R: 'static + ToGodot,
+	{
+		let (routine, alive) = crate::batch::host_routine(items, make);
+		CoroutineBatch::new(self.start_coroutine(routine), alive)
+	}
+
+	/// Spawns a coroutine that sends an HTTP GET request to `url`, through an [HttpRequest](godot::classes::HttpRequest) node.
+	///
+	/// Once the response arrives, the coroutine emits [value](SIGNAL_VALUE) with the response's status code, 
+	/// then finishes with the response's body decoded as UTF-8 (a [GString]), whatever the status code.
+	///
+	/// If the request can't be sent or fails (e.g. invalid url, connection error, timeout), 
+	/// logs an error and finishes with `nil` instead, without emitting [value](SIGNAL_VALUE).
+	///
+	/// The request node is a child of the coroutine, it's freed once the request completes, or along with the coroutine if it's killed.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use godot::prelude::*;
+	/// use gdext_coroutines::prelude::*;
+	///
+	/// fn showcase_http_get(node: Gd<Node>) {
+	///     let request = node.http_get("https://example.com/news.txt");
+	///
+	///     node.start_coroutine(
+	///         #[coroutine] move || {
+	///             let response = SignalAwaiter::new(request, SIGNAL_FINISHED);
+	///             yield response.yield_wait();
+	///
+	///             match response.args().and_then(|args| args.first()?.try_to::<GString>().ok()) {
+	///                 Some(news) => godot_print!("News: {news}"),
+	///                 None => godot_print!("Couldn't fetch the news."),
+	///             }
+	///         });
+	/// }
+	/// ```
+	#[cfg(not(feature = "stable"))]
+	#[cfg_attr(docsrs, doc(cfg(not(feature = "stable"))))]
+	fn http_get(&self, url: impl Into<GString>) -> Gd<SpireCoroutine> {
+		self.start_coroutine(crate::http::http_get_routine(url.into()))
+	}
 }
 
 impl<TSelf> StartCoroutine for Gd<TSelf>
@@ -76,7 +233,7 @@ impl<TSelf> StartCoroutine for Gd<TSelf>
 {
 	fn coroutine<R>(
 		&self,
-		f: impl 'static + Unpin + Coroutine<(), Yield = SpireYield, Return = R>,
+		f: impl 'static + PollRoutine<Return = R>,
 	) -> CoroutineBuilder<R>
 		where
 			R: 'static + ToGodot,
@@ -91,7 +248,7 @@ impl<T> StartCoroutine for &T
 {
 	fn coroutine<R>(
 		&self,
-		f: impl 'static + Unpin + Coroutine<(), Yield = SpireYield, Return = R>,
+		f: impl 'static + PollRoutine<Return = R>,
 	) -> CoroutineBuilder<R>
 		where
 			R: 'static + ToGodot,
@@ -107,7 +264,7 @@ impl<T> StartCoroutine for &mut T
 {
 	fn coroutine<R>(
 		&self,
-		f: impl 'static + Unpin + Coroutine<(), Yield = SpireYield, Return = R>,
+		f: impl 'static + PollRoutine<Return = R>,
 	) -> CoroutineBuilder<R>
 		where
 			R: 'static + ToGodot,
@@ -115,4 +272,110 @@ impl<T> StartCoroutine for &mut T
 		let base = self.base_field().to_gd();
 		CoroutineBuilder::new_coroutine(base.upcast(), f)
 	}
-}
\ No newline at end of file
+}
+
+/// Creates a coroutine builder whose coroutine will be a child of `parent`, 
+/// for callers that don't inherit [Node] and therefore can't use [StartCoroutine].
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use godot::classes::{Engine, SceneTree};
+/// use godot::prelude::*;
+/// use gdext_coroutines::prelude::*;
+///
+/// #[derive(GodotClass)]
+/// #[class(init, base = RefCounted)]
+/// struct QuestController {}
+///
+/// #[godot_api]
+/// impl QuestController {
+///     #[func]
+///     fn begin(&self) {
+///         let root = Engine::singleton()
+///             .get_main_loop()
+///             .unwrap()
+///             .cast::<SceneTree>()
+///             .get_root()
+///             .unwrap();
+///
+///         coroutine_on(root,
+///             #[coroutine] || {
+///                 yield seconds(3.0);
+///                 godot_print!("Quest started!");
+///             })
+///             .spawn();
+///     }
+/// }
+/// ```
+pub fn coroutine_on<R>(
+	parent: Gd<impl Inherits<Node>>,
+	f: impl 'static + PollRoutine<Return = R>,
+) -> CoroutineBuilder<R>
+	where
+		R: 'static + ToGodot,
+{
+	CoroutineBuilder::new_coroutine(parent.upcast(), f)
+}
+
+/// Spawns and starts a new coroutine with default settings as a child of `parent`, see [coroutine_on].
+pub fn start_coroutine_on<R>(
+	parent: Gd<impl Inherits<Node>>,
+	f: impl 'static + PollRoutine<Return = R>,
+) -> Gd<SpireCoroutine>
+	where
+		R: 'static + ToGodot,
+{
+	coroutine_on(parent, f).spawn()
+}
+
+/// Spawns and starts a fire-and-forget coroutine with default settings, as a child of the scene tree's root.
+///
+/// Meant for one-shot global effects (e.g. screen flashes, sound stingers) that shouldn't be tied to the caller's lifetime,
+/// which is why no handle is returned: nothing can keep the coroutine's node alive, nor be left holding a dangling handle.
+///
+/// Cleanup guarantees:
+/// - Once the coroutine finishes, its node is removed from the tree and freed.
+/// - If the coroutine's closure panics, the coroutine is killed and its node is freed just the same (the closure itself is leaked,
+///   see [SpireCoroutine]).
+/// - The node only outlives its closure if the whole tree is freed first, e.g. when quitting the game.
+///
+/// Logs an error and does nothing if there's no scene tree, e.g. if called before the main loop starts.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use godot::prelude::*;
+/// use gdext_coroutines::prelude::*;
+///
+/// fn showcase_start_coroutine_detached_forget(mut flash: Gd<Node2D>) {
+///     start_coroutine_detached_forget(
+///         #[coroutine] move || {
+///             flash.set_visible(true);
+///             yield seconds(0.1);
+///             flash.set_visible(false);
+///         });
+/// }
+/// ```
+pub fn start_coroutine_detached_forget<R>(
+	f: impl 'static + PollRoutine<Return = R>,
+)
+	where
+		R: 'static + ToGodot,
+{
+	let root =
+		Engine::singleton()
+			.get_main_loop()
+			.and_then(|main_loop| main_loop.try_cast::<SceneTree>().ok())
+			.and_then(|tree| tree.get_root());
+
+	let Some(root) = root
+	else {
+		godot_error!("Cannot start a detached coroutine: there's no scene tree.");
+		return;
+	};
+
+	start_coroutine_on(root, f);
+}
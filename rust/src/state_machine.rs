@@ -0,0 +1,82 @@
+//! Coroutines written as explicit state machines, without `yield` syntax.
+//!
+//! Unlike `yield`-based coroutines, these don't need any unstable features:
+//! with the feature "stable", this crate builds on a stable toolchain, keeping only the APIs that don't rely on `yield`.
+
+use crate::routine::{PollRoutine, RoutineState};
+use crate::yielding::SpireYield;
+
+/// What a [StateMachineCoroutine] does after each step.
+pub enum Step<R> {
+	/// Waits on the yield, then runs the next step.
+	Wait(SpireYield),
+	/// Finishes the coroutine with the result.
+	Done(R),
+}
+
+/// A coroutine that repeatedly invokes `step` with mutable access to its `state`, see [state_machine].
+pub struct StateMachineCoroutine<S, F> {
+	state: S,
+	step: F,
+}
+
+/// Creates a coroutine that runs `step` until it returns [Step::Done].
+///
+/// Each call to `step` is equivalent to the code between two `yield` statements: 
+/// returning [Step::Wait] waits on the yield, then `step` is called again.
+/// Any data that needs to survive between steps must be stored in `state`.
+///
+/// The resulting coroutine is started like any other, with [StartCoroutine](crate::prelude::StartCoroutine).
+///
+/// # Example
+///
+/// ```no_run
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// enum Countdown {
+///     Counting(u32),
+///     Finished,
+/// }
+///
+/// fn showcase_state_machine(node: Gd<Node>) {
+///     node.start_coroutine(
+///         state_machine(Countdown::Counting(3), |state| {
+///             match state {
+///                 Countdown::Counting(0) => {
+///                     *state = Countdown::Finished;
+///                     Step::Wait(seconds(1.0))
+///                 }
+///                 Countdown::Counting(count) => {
+///                     godot_print!("{count}...");
+///                     *count -= 1;
+///                     Step::Wait(seconds(1.0))
+///                 }
+///                 Countdown::Finished => {
+///                     godot_print!("Go!");
+///                     Step::Done(())
+///                 }
+///             }
+///         }));
+/// }
+/// ```
+pub fn state_machine<S, F, R>(state: S, step: F) -> StateMachineCoroutine<S, F>
+	where
+		F: FnMut(&mut S) -> Step<R>,
+{
+	StateMachineCoroutine { state, step }
+}
+
+impl<S, F, R> PollRoutine for StateMachineCoroutine<S, F>
+	where
+		F: FnMut(&mut S) -> Step<R>,
+{
+	type Return = R;
+
+	fn resume(&mut self) -> RoutineState<R> {
+		match (self.step)(&mut self.state) {
+			Step::Wait(_yield) => RoutineState::Yielded(_yield),
+			Step::Done(result) => RoutineState::Complete(result),
+		}
+	}
+}
@@ -0,0 +1,36 @@
+//! Global counters for detecting leaked coroutines, only tracked with the feature "stats".
+
+#[cfg(feature = "stats")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(feature = "stats")]
+static SPAWNED: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "stats")]
+static ALIVE: AtomicU64 = AtomicU64::new(0);
+
+/// Returns `(spawned, alive)`:
+/// - `spawned`: how many coroutines were spawned since the program started.
+/// - `alive`: how many of those haven't ended (finished, killed or freed) yet.
+///
+/// During development, an `alive` count that keeps growing is a sign that some system spawns coroutines 
+/// that never end.
+///
+/// Requires the feature "stats", without it no counting happens.
+#[cfg(feature = "stats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+pub fn coroutine_stats() -> (u64, u64) {
+	(SPAWNED.load(Ordering::Relaxed), ALIVE.load(Ordering::Relaxed))
+}
+
+pub(crate) fn on_spawn() {
+	#[cfg(feature = "stats")]
+	{
+		SPAWNED.fetch_add(1, Ordering::Relaxed);
+		ALIVE.fetch_add(1, Ordering::Relaxed);
+	}
+}
+
+pub(crate) fn on_end() {
+	#[cfg(feature = "stats")]
+	ALIVE.fetch_sub(1, Ordering::Relaxed);
+}
@@ -0,0 +1,131 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use godot::prelude::*;
+
+use crate::prelude::*;
+
+/// Drives a set of coroutines with an explicit, virtual time instead of the engine's frames.
+///
+/// Meant for timeline-based tools like cutscene editors, where dragging a playhead forward should
+/// make every coroutine of the cutscene catch up with it.
+///
+/// Member coroutines are paused so that the engine stops polling them, then [set_virtual_time](Self::set_virtual_time)
+/// fast-forwards all of them (through [SpireCoroutine::advance_time]) by however much the virtual time moved.
+/// Since coroutines can't be rewound, moving the time backward does nothing: the clock stays at its latest time.
+///
+/// Cloning the clock creates another handle to the same clock.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use godot::prelude::*;
+/// use gdext_coroutines::prelude::*;
+///
+/// fn showcase_virtual_clock(cutscene_root: Gd<Node>, mut camera: Gd<Node3D>) -> VirtualClock {
+///     let clock = VirtualClock::new(cutscene_root, 1.0 / 60.0);
+///
+///     clock.start(
+///         #[coroutine] move || {
+///             yield seconds(2.0);
+///             camera.set_position(Vector3::new(0.0, 5.0, 10.0));
+///         });
+///
+///     clock
+/// }
+///
+/// // Called whenever the editor's playhead moves.
+/// fn on_playhead_moved(clock: &VirtualClock, time: f64) {
+///     clock.set_virtual_time(time);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct VirtualClock {
+	owner: Gd<Node>,
+	inner: Rc<RefCell<ClockInner>>,
+}
+
+struct ClockInner {
+	time: f64,
+	max_step_seconds: f64,
+	coroutines: Vec<Gd<SpireCoroutine>>,
+}
+
+impl VirtualClock {
+	/// Creates a clock at time 0, whose [start](Self::start) spawns coroutines as children of `owner`.
+	///
+	/// Whenever the time moves forward, members are polled in increments of at most `max_step_seconds`,
+	/// which is also how long a single [frame](frames) lasts for them, see [SpireCoroutine::advance_time].
+	pub fn new(owner: Gd<impl Inherits<Node>>, max_step_seconds: f64) -> Self {
+		Self {
+			owner: owner.upcast(),
+			inner: Rc::new(RefCell::new(ClockInner {
+				time: 0.0,
+				max_step_seconds,
+				coroutines: Vec::new(),
+			})),
+		}
+	}
+
+	/// Spawns `f` with default settings as a child of the clock's owner, then adds it to the clock.
+	pub fn start<R>(
+		&self,
+		f: impl 'static + PollRoutine<Return = R>,
+	) -> Gd<SpireCoroutine>
+		where
+			R: 'static + ToGodot,
+	{
+		self.add(self.owner.coroutine(f).spawn_paused())
+	}
+
+	/// Pauses an already spawned coroutine, then adds it to the clock, the coroutine may belong to any owner.
+	///
+	/// The coroutine only experiences the time that passes after it was added.
+	pub fn add(&self, mut coroutine: Gd<SpireCoroutine>) -> Gd<SpireCoroutine> {
+		coroutine.bind_mut().pause();
+
+		let mut inner = self.inner.borrow_mut();
+		inner.coroutines.retain(|coroutine| !coroutine.is_finished());
+		inner.coroutines.push(coroutine.clone());
+		coroutine
+	}
+
+	/// Returns the clock's current time.
+	pub fn time(&self) -> f64 {
+		self.inner.borrow().time
+	}
+
+	/// Returns the coroutines driven by the clock that haven't finished yet.
+	pub fn coroutines(&self) -> Vec<Gd<SpireCoroutine>> {
+		let mut inner = self.inner.borrow_mut();
+		inner.coroutines.retain(|coroutine| !coroutine.is_finished());
+		inner.coroutines.clone()
+	}
+
+	/// Moves the clock to `time`, fast-forwarding every member by the difference.
+	///
+	/// If `time` is earlier than the clock's current time, it's clamped: nothing happens.
+	///
+	/// Returns the clock's time after the call.
+	pub fn set_virtual_time(&self, time: f64) -> f64 {
+		let (delta, max_step_seconds) = {
+			let mut inner = self.inner.borrow_mut();
+
+			if time <= inner.time {
+				return inner.time;
+			}
+
+			let delta = time - inner.time;
+			inner.time = time;
+			(delta, inner.max_step_seconds)
+		};
+
+		// Borrow released, members may add coroutines to the clock while being advanced.
+		for mut coroutine in self.coroutines() {
+			coroutine.bind_mut().advance_time(delta, max_step_seconds);
+		}
+
+		time
+	}
+}
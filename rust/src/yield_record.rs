@@ -0,0 +1,40 @@
+use crate::yielding::SpireYield;
+
+/// A single yield processed by a coroutine, see [CoroutineBuilder::record_yields](crate::prelude::CoroutineBuilder::record_yields).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct YieldRecord {
+	/// What the coroutine started waiting on.
+	pub kind: YieldKind,
+	/// How many seconds the coroutine had been running for when it yielded,
+	/// in the same time base as [SpireCoroutine::get_elapsed_seconds](crate::prelude::SpireCoroutine::get_elapsed_seconds).
+	///
+	/// Yields produced later in the same poll (e.g. after `frames(0)`) account for the time consumed by the previous ones.
+	pub elapsed_seconds: f64,
+	/// On which of the coroutine's polls it yielded, starting at 1 for the first poll,
+	/// see [SpireCoroutine::get_elapsed_frames](crate::prelude::SpireCoroutine::get_elapsed_frames).
+	pub elapsed_frames: i64,
+}
+
+/// The kind (and duration) of a [YieldRecord].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum YieldKind {
+	/// A [frames](crate::prelude::frames) wait, negative amounts are recorded as 0.
+	Frames(i64),
+	/// A [seconds](crate::prelude::seconds) wait.
+	Seconds(f64),
+	/// A [seconds_unscaled](crate::prelude::seconds_unscaled) wait.
+	SecondsUnscaled(f64),
+	/// Any other yield, e.g. [wait_until](crate::prelude::wait_until) or a [KeepWaiting](crate::prelude::KeepWaiting) implementation.
+	Custom,
+}
+
+impl YieldKind {
+	pub(crate) fn of(_yield: &SpireYield) -> Self {
+		match _yield {
+			SpireYield::Frames(frames) => YieldKind::Frames(*frames),
+			SpireYield::Seconds(seconds) => YieldKind::Seconds(*seconds),
+			SpireYield::SecondsUnscaled(seconds) => YieldKind::SecondsUnscaled(*seconds),
+			SpireYield::Dyn(_) | SpireYield::Inline(_) => YieldKind::Custom,
+		}
+	}
+}
@@ -1,14 +1,213 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use godot::classes::{Engine, RandomNumberGenerator, Time};
 use godot::prelude::*;
+use smallbox::space::S4;
+use smallbox::{smallbox, SmallBox};
 
 use crate::prelude::*;
 
 /// Possible wait modes for coroutines.
 /// 
 /// See [frames], [seconds] and [KeepWaiting]
+///
+/// Timed yields can be stored in `const` items, e.g. `const SHORT_PAUSE: SpireYield = seconds(0.2);`, 
+/// for `static` items and tables of timings, see [WaitStep].
 pub enum SpireYield {
 	Frames(i64),
 	Seconds(f64),
+	/// Same as [SpireYield::Seconds], but compensates the engine's time scale, see [seconds_unscaled].
+	SecondsUnscaled(f64),
 	Dyn(Box<dyn KeepWaiting>),
+	/// Same as [SpireYield::Dyn], but waiters that fit in 4 words are stored inline, skipping the heap allocation.
+	/// 
+	/// Larger waiters are transparently moved to the heap.
+	/// 
+	/// Prefer this when re-yielding conditions every frame, see [SpireYield::inline].
+	Inline(SmallBox<dyn KeepWaiting, S4>),
+}
+
+/// Waits for a single frame, like `yield return null` does in Unity.
+impl Default for SpireYield {
+	fn default() -> Self {
+		frames(1)
+	}
+}
+
+impl SpireYield {
+	/// Creates a [SpireYield::Inline] from `waiter`.
+	pub fn inline(waiter: impl KeepWaiting + 'static) -> SpireYield {
+		SpireYield::Inline(smallbox!(waiter))
+	}
+
+	/// Returns the custom waiter, if this is either [SpireYield::Dyn] or [SpireYield::Inline].
+	pub fn as_dyn_mut(&mut self) -> Option<&mut dyn KeepWaiting> {
+		match self {
+			SpireYield::Dyn(dyn_yield) => Some(dyn_yield.as_mut()),
+			SpireYield::Inline(inline_yield) => Some(&mut **inline_yield),
+			SpireYield::Frames(_) | SpireYield::Seconds(_) | SpireYield::SecondsUnscaled(_) => None,
+		}
+	}
+
+	/// Same as [as_dyn_mut](Self::as_dyn_mut), but borrows immutably.
+	pub fn as_dyn(&self) -> Option<&dyn KeepWaiting> {
+		match self {
+			SpireYield::Dyn(dyn_yield) => Some(dyn_yield.as_ref()),
+			SpireYield::Inline(inline_yield) => Some(&**inline_yield),
+			SpireYield::Frames(_) | SpireYield::Seconds(_) | SpireYield::SecondsUnscaled(_) => None,
+		}
+	}
+
+	/// Returns a human-readable description of what this yield waits on, e.g. `"waiting on 2.50 seconds"`.
+	///
+	/// Custom yields are described by [KeepWaiting::describe], those that don't implement it are described generically.
+	pub fn describe(&self) -> String {
+		match self {
+			SpireYield::Frames(1) => "waiting on 1 frame".to_string(),
+			SpireYield::Frames(frames) => format!("waiting on {frames} frames"),
+			SpireYield::Seconds(seconds) => format!("waiting on {seconds:.2} seconds"),
+			SpireYield::SecondsUnscaled(seconds) => format!("waiting on {seconds:.2} unscaled seconds"),
+			SpireYield::Dyn(_) | SpireYield::Inline(_) => {
+				self.as_dyn()
+				    .and_then(KeepWaiting::describe)
+				    .unwrap_or_else(|| "waiting on a custom yield".to_string())
+			}
+		}
+	}
+
+	/// Creates a yield that waits on `self`, then on `next`.
+	///
+	/// Chains of timed yields behave exactly like yielding each of them in order, 
+	/// the part of a frame's delta time left over by a [seconds] wait is carried to the next one.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// #![feature(coroutines)]
+	/// use gdext_coroutines::prelude::*;
+	/// use godot::prelude::*;
+	///
+	/// fn showcase_then_wait(node: Gd<Node>, door: Gd<Node2D>) {
+	///      node.start_coroutine(
+	///           #[coroutine] move || {
+	///                yield seconds(1.0)
+	///                     .then_wait(frames(3))
+	///                     .then_wait(wait_until(move || door.is_visible()));
+	///                godot_print!("Waited for 1 second, then 3 frames, then for the door to be visible! Resuming...");
+	///           });
+	/// }
+	/// ```
+	pub fn then_wait(self, next: SpireYield) -> SpireYield {
+		SpireYield::Dyn(Box::new(Sequence {
+			steps: VecDeque::from([self, next]),
+			unconsumed_delta: None,
+		}))
+	}
+}
+
+/// Waits on each yield in order, see [SpireYield::then_wait].
+struct Sequence {
+	steps: VecDeque<SpireYield>,
+	unconsumed_delta: Option<f64>,
+}
+
+impl KeepWaiting for Sequence {
+	fn keep_waiting(&mut self, delta_time: f64) -> bool {
+		self.poll_wait(delta_time) == WaitOutcome::Continue
+	}
+
+	fn poll_wait(&mut self, delta_time: f64) -> WaitOutcome {
+		let mut delta_time = delta_time;
+
+		// Mirrors how the coroutine polls its own yields: the remaining delta is passed on to the next step in the same poll.
+		while let Some(step) = self.steps.front_mut() {
+			match step {
+				SpireYield::Frames(frames) => {
+					if *frames > 0 {
+						*frames -= 1;
+						return WaitOutcome::Continue;
+					}
+				}
+				SpireYield::Seconds(seconds) => {
+					*seconds -= delta_time;
+
+					if *seconds > 0.0 {
+						return WaitOutcome::Continue;
+					}
+
+					delta_time = -*seconds;
+				}
+				SpireYield::SecondsUnscaled(seconds) => {
+					match count_down_unscaled(seconds, delta_time) {
+						Some(unconsumed_delta) => delta_time = unconsumed_delta,
+						None => return WaitOutcome::Continue,
+					}
+				}
+				SpireYield::Dyn(waiter) => {
+					match poll_step(waiter.as_mut(), delta_time) {
+						Ok(unconsumed_delta) => delta_time = unconsumed_delta,
+						Err(outcome) => return outcome,
+					}
+				}
+				SpireYield::Inline(waiter) => {
+					match poll_step(&mut **waiter, delta_time) {
+						Ok(unconsumed_delta) => delta_time = unconsumed_delta,
+						Err(outcome) => return outcome,
+					}
+				}
+			}
+
+			self.steps.pop_front();
+		}
+
+		self.unconsumed_delta = Some(delta_time);
+		WaitOutcome::Resume
+	}
+
+	fn unconsumed_delta(&self) -> Option<f64> {
+		self.unconsumed_delta
+	}
+
+	fn describe(&self) -> Option<String> {
+		let current = self.steps.front()?.describe();
+
+		match self.steps.len() - 1 {
+			0 => Some(current),
+			1 => Some(format!("{current}, then on 1 more step")),
+			more => Some(format!("{current}, then on {more} more steps")),
+		}
+	}
+}
+
+/// Returns the unconsumed delta time if `waiter` resumed, otherwise the outcome that interrupts the [Sequence].
+fn poll_step(waiter: &mut dyn KeepWaiting, delta_time: f64) -> Result<f64, WaitOutcome> {
+	match waiter.poll_wait(delta_time) {
+		WaitOutcome::Resume => Ok(waiter.unconsumed_delta().unwrap_or(delta_time)),
+		outcome @ (WaitOutcome::Continue | WaitOutcome::Abort) => Err(outcome),
+	}
+}
+
+/// Polls a yield nested inside a combinator, forwarding its [WaitOutcome] so that an [Abort](WaitOutcome::Abort) isn't swallowed.
+///
+/// Yields without a [KeepWaiting] implementation (e.g. the `frames(0)` returned for freed coroutines) are done waiting.
+pub(crate) fn poll_nested(wait: &mut SpireYield, delta_time: f64) -> WaitOutcome {
+	wait.as_dyn_mut().map_or(WaitOutcome::Resume, |waiter| waiter.poll_wait(delta_time))
+}
+
+/// The result of polling a [KeepWaiting] yield, see [KeepWaiting::poll_wait].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WaitOutcome {
+	/// The coroutine keeps waiting.
+	Continue,
+	/// The coroutine resumes execution.
+	Resume,
+	/// The coroutine is [killed](SpireCoroutine::kill), invoking its [on_cancel](CoroutineBuilder::on_cancel) callbacks.
+	///
+	/// Useful for yields that wrap fallible resources, e.g. awaiting a download that failed.
+	Abort,
 }
 
 pub trait KeepWaiting {
@@ -19,6 +218,33 @@ pub trait KeepWaiting {
 	/// This will be polled on every [_process](INode::process) or [_physics_process](INode::physics_process), 
 	/// depending on the configuration.
 	fn keep_waiting(&mut self, delta_time: f64) -> bool;
+
+	/// The method the coroutine actually calls when polled, override it to be able to [abort](WaitOutcome::Abort) the coroutine.
+	///
+	/// By default, maps [keep_waiting](Self::keep_waiting) to either [WaitOutcome::Continue] or [WaitOutcome::Resume].
+	fn poll_wait(&mut self, delta_time: f64) -> WaitOutcome {
+		if self.keep_waiting(delta_time) {
+			WaitOutcome::Continue
+		} else {
+			WaitOutcome::Resume
+		}
+	}
+
+	/// After [poll_wait](Self::poll_wait) returns [WaitOutcome::Resume], how much of its `delta_time` wasn't consumed by the wait.
+	///
+	/// The coroutine passes it on to whatever it waits on next in the same poll, like it does with the leftover of [seconds].
+	/// By default, returns `None`, meaning the whole `delta_time` is passed on.
+	fn unconsumed_delta(&self) -> Option<f64> {
+		None
+	}
+
+	/// A human-readable description of what the yield waits on, e.g. `"waiting on signal 'pressed' of <Button#123>"`.
+	///
+	/// Shown by [SpireCoroutine::get_awaiting_description], which makes it easier to find out why a coroutine is stuck.
+	/// By default, returns `None`, meaning the yield is described generically.
+	fn describe(&self) -> Option<String> {
+		None
+	}
 }
 
 impl<T: FnMut() -> bool> KeepWaiting for T {
@@ -27,20 +253,76 @@ impl<T: FnMut() -> bool> KeepWaiting for T {
 	}
 }
 
+/// Waits until whatever coroutine occupies the node is finished.
+///
+/// Doesn't notice if the node is reused by a [CoroutinePool], prefer [wait_until_finished](WaitUntilFinished::wait_until_finished).
 impl KeepWaiting for Gd<SpireCoroutine> {
 	fn keep_waiting(&mut self, _delta_time: f64) -> bool {
-		// Coroutines auto-destroy themselves when they finish
-		self.is_instance_valid()
+		!self.is_finished()
+	}
+
+	fn describe(&self) -> Option<String> {
+		Some(describe_coroutine(self))
+	}
+}
+
+fn describe_coroutine(coroutine: &Gd<SpireCoroutine>) -> String {
+	if coroutine.is_instance_valid() {
+		format!("waiting on coroutine '{}'", coroutine.get_name())
+	} else {
+		"waiting on a freed coroutine".to_string()
+	}
+}
+
+/// Waits until a specific run of a coroutine ends, even if its node is reused by a [CoroutinePool] afterward.
+struct UntilFinished {
+	coroutine: Gd<SpireCoroutine>,
+	generation: u64,
+}
+
+impl KeepWaiting for UntilFinished {
+	fn keep_waiting(&mut self, _delta_time: f64) -> bool {
+		!run_ended(&self.coroutine, self.generation)
+	}
+
+	fn describe(&self) -> Option<String> {
+		Some(describe_coroutine(&self.coroutine))
+	}
+}
+
+/// Returns true once the run of `coroutine` started at `generation` ended,
+/// which includes its node being freed or reused by a [CoroutinePool].
+pub(crate) fn run_ended(coroutine: &Gd<SpireCoroutine>, generation: u64) -> bool {
+	!coroutine.is_instance_valid() || {
+		let coroutine = coroutine.bind();
+		coroutine.generation != generation || coroutine.is_finished()
 	}
 }
 
+/// Creates a yield that waits until a coroutine ends (finishes or is killed).
+///
+/// The awaiting coroutine resumes on its first poll after the awaited coroutine ended, regardless of their [PollMode]s:
+/// - Same poll mode: if the awaited coroutine is polled first, on the same frame, otherwise on the next one.
+/// - Different poll modes: on the awaiting coroutine's next poll, e.g. a [PollMode::Physics] coroutine awaiting a 
+///   [PollMode::Process] one resumes on the next physics tick.
+///
+/// Completion is detected through [is_finished](SpireCoroutine::is_finished), it does not wait for the awaited node to be freed.
 pub trait WaitUntilFinished {
 	fn wait_until_finished(&self) -> SpireYield;
 }
 
 impl WaitUntilFinished for Gd<SpireCoroutine> {
 	fn wait_until_finished(&self) -> SpireYield {
-		SpireYield::Dyn(Box::new(self.clone()))
+		if !self.is_instance_valid() {
+			return SpireYield::Frames(0);
+		}
+
+		let generation = self.bind().generation;
+
+		SpireYield::inline(UntilFinished {
+			coroutine: self.clone(),
+			generation,
+		})
 	}
 }
 
@@ -50,9 +332,237 @@ impl WaitUntilFinished for SpireCoroutine {
 	}
 }
 
+/// Coroutine resumes execution once every coroutine in `coroutines` has ended (finished, was killed or freed).
+///
+/// Unlike yielding [wait_until_finished](WaitUntilFinished::wait_until_finished) for each coroutine in sequence,
+/// all coroutines are checked on every poll, so it doesn't matter in which order they end.
+///
+/// To also get the coroutines' results, see [join_results].
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_all(node: Gd<Node>, doors: Vec<Gd<SpireCoroutine>>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield wait_all(doors);
+///                godot_print!("All doors opened! Resuming...");
+///           });
+/// }
+/// ```
+pub fn wait_all(coroutines: impl IntoIterator<Item = Gd<SpireCoroutine>>) -> SpireYield {
+	let waits =
+		coroutines
+			.into_iter()
+			.map(|coroutine| coroutine.wait_until_finished())
+			.collect::<Vec<_>>();
+
+	let total = waits.len();
+	SpireYield::Dyn(Box::new(WaitAll { waits, total }))
+}
+
+struct WaitAll {
+	waits: Vec<SpireYield>,
+	total: usize,
+}
+
+impl KeepWaiting for WaitAll {
+	fn keep_waiting(&mut self, delta_time: f64) -> bool {
+		self.poll_wait(delta_time) == WaitOutcome::Continue
+	}
+
+	fn poll_wait(&mut self, delta_time: f64) -> WaitOutcome {
+		let mut aborted = false;
+
+		self.waits.retain_mut(|wait| {
+			match poll_nested(wait, delta_time) {
+				WaitOutcome::Continue => true,
+				WaitOutcome::Resume => false,
+				WaitOutcome::Abort => {
+					aborted = true;
+					false
+				}
+			}
+		});
+
+		if aborted {
+			WaitOutcome::Abort
+		} else if self.waits.is_empty() {
+			WaitOutcome::Resume
+		} else {
+			WaitOutcome::Continue
+		}
+	}
+
+	fn describe(&self) -> Option<String> {
+		Some(format!("waiting on {} of {} coroutines", self.waits.len(), self.total))
+	}
+}
+
+/// Coroutine resumes execution once any coroutine in `coroutines` has ended (finished, was killed or freed).
+///
+/// Also returns a cell that will contain the index (in `coroutines`) of the first coroutine that ended,
+/// if several ended since the last poll, the lowest index is picked.
+/// If `coroutines` is empty, the coroutine resumes right away and the cell stays `None`.
+///
+/// The other coroutines are left untouched, [kill](SpireCoroutine::kill) them if they're no longer needed.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_any(node: Gd<Node>, mut work: Gd<SpireCoroutine>) {
+///      let timeout = node.start_coroutine(#[coroutine] || { yield seconds(5.0); });
+///
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                let (wait, first) = wait_any([work.clone(), timeout]);
+///                yield wait;
+///
+///                if first.get() == Some(1) {
+///                     godot_print!("Work timed out!");
+///                     work.bind_mut().kill();
+///                }
+///           });
+/// }
+/// ```
+pub fn wait_any(coroutines: impl IntoIterator<Item = Gd<SpireCoroutine>>) -> (SpireYield, Rc<Cell<Option<usize>>>) {
+	let waits =
+		coroutines
+			.into_iter()
+			.map(|coroutine| coroutine.wait_until_finished())
+			.collect::<Vec<_>>();
+
+	let first = Rc::new(Cell::new(None));
+
+	let any = WaitAny {
+		waits,
+		first: first.clone(),
+	};
+
+	(SpireYield::Dyn(Box::new(any)), first)
+}
+
+struct WaitAny {
+	waits: Vec<SpireYield>,
+	first: Rc<Cell<Option<usize>>>,
+}
+
+impl KeepWaiting for WaitAny {
+	fn keep_waiting(&mut self, delta_time: f64) -> bool {
+		self.poll_wait(delta_time) == WaitOutcome::Continue
+	}
+
+	fn poll_wait(&mut self, delta_time: f64) -> WaitOutcome {
+		if self.waits.is_empty() {
+			return WaitOutcome::Resume;
+		}
+
+		for (index, wait) in self.waits.iter_mut().enumerate() {
+			match poll_nested(wait, delta_time) {
+				WaitOutcome::Continue => {}
+				WaitOutcome::Resume => {
+					self.first.set(Some(index));
+					return WaitOutcome::Resume;
+				}
+				WaitOutcome::Abort => return WaitOutcome::Abort,
+			}
+		}
+
+		WaitOutcome::Continue
+	}
+
+	fn describe(&self) -> Option<String> {
+		Some(format!("waiting on the first of {} coroutines", self.waits.len()))
+	}
+}
+
+/// Coroutine resumes execution once `node` is freed.
+///
+/// This relies on [instance validity](Gd::is_instance_valid), not on [queue_free](Node::queue_free) scheduling:
+/// a node that's queued for deletion is still valid until the end of the frame,
+/// so the coroutine only resumes on the first poll after the node is actually freed.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_until_freed(node: Gd<Node>, popup: Gd<Node>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield wait_until_freed(popup);
+///                godot_print!("Popup was freed! Resuming...");
+///           });
+/// }
+///
+/// ```
+pub fn wait_until_freed(node: Gd<impl Inherits<Node>>) -> SpireYield {
+	let node = node.upcast::<Node>();
+	SpireYield::inline(move || node.is_instance_valid())
+}
+
+/// Coroutine resumes execution once `node` is [ready](Node::is_node_ready),
+/// meaning it entered the tree and its [_ready](INode::ready) was called.
+///
+/// Useful when starting a coroutine right after instantiating a scene, before it was added to the tree.
+///
+/// If `node` is freed before becoming ready, a warning is logged and the coroutine resumes anyway.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_for_node_ready(node: Gd<Node>, bullet: Gd<Node2D>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield wait_for_node_ready(bullet.clone());
+///                let mut bullet = bullet;
+///                bullet.set_global_position(Vector2::new(100.0, 0.0));
+///           });
+/// }
+/// ```
+pub fn wait_for_node_ready(node: Gd<impl Inherits<Node>>) -> SpireYield {
+	SpireYield::inline(NodeReady(node.upcast()))
+}
+
+struct NodeReady(Gd<Node>);
+
+impl KeepWaiting for NodeReady {
+	fn keep_waiting(&mut self, _delta_time: f64) -> bool {
+		if !self.0.is_instance_valid() {
+			godot_warn!("wait_for_node_ready: node was freed before becoming ready, resuming coroutine.");
+			return false;
+		}
+
+		!self.0.is_node_ready()
+	}
+
+	fn describe(&self) -> Option<String> {
+		if self.0.is_instance_valid() {
+			Some(format!("waiting until {} is ready", self.0))
+		} else {
+			Some("waiting until a freed node is ready".to_string())
+		}
+	}
+}
+
 /// Coroutine pauses execution as long as `f` returns true.
 /// 
 /// `f` is invoked whenever the coroutine is polled.
+/// It may either take no arguments, or take the poll's delta time, see [WaitCondition].
 /// 
 /// If un-paused, the coroutine is polled either on [_process](INode::process) 
 /// or [_physics_process](INode::physics_process)
@@ -74,13 +584,18 @@ impl WaitUntilFinished for SpireCoroutine {
 /// }
 ///
 /// ```
-pub fn wait_while(f: impl FnMut() -> bool + 'static) -> SpireYield {
-	SpireYield::Dyn(Box::new(f))
+pub fn wait_while<M: 'static>(f: impl WaitCondition<M> + 'static) -> SpireYield {
+	SpireYield::inline(Condition {
+		condition: f,
+		waits_while: true,
+		marker: PhantomData,
+	})
 }
 
 /// Coroutine resumes execution once `f` returns true.
 /// 
 /// `f` is invoked whenever the coroutine is polled. 
+/// It may either take no arguments, or take the poll's delta time, see [WaitCondition].
 /// 
 /// If un-paused, the coroutine is polled either on [process](INode::process) 
 /// or [physics_process](INode::physics_process))
@@ -102,8 +617,362 @@ pub fn wait_while(f: impl FnMut() -> bool + 'static) -> SpireYield {
 /// }
 ///
 /// ```
-pub fn wait_until(mut f: impl FnMut() -> bool + 'static) -> SpireYield {
-	SpireYield::Dyn(Box::new(move || !f()))
+pub fn wait_until<M: 'static>(f: impl WaitCondition<M> + 'static) -> SpireYield {
+	SpireYield::inline(Condition {
+		condition: f,
+		waits_while: false,
+		marker: PhantomData,
+	})
+}
+
+/// A condition checked by [wait_while] and [wait_until] whenever the coroutine is polled.
+///
+/// Implemented for closures that ignore the delta time (`FnMut() -> bool`), and for closures that take it (`FnMut(f64) -> bool`).
+/// `Marker` only tells both implementations apart, it's always inferred.
+///
+/// Closures that take the delta time need their argument's type annotated, since it cannot be inferred from the bound.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use godot::classes::AnimatedSprite2D;
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_condition(node: Gd<Node>, sprite: Gd<AnimatedSprite2D>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                // Resumes once the sprite has been playing for 2 seconds in total, excluding pauses.
+///                let mut playing_for = 0.0;
+///                yield wait_until(move |delta: f64| {
+///                     if sprite.is_playing() {
+///                          playing_for += delta;
+///                     }
+///
+///                     playing_for >= 2.0
+///                });
+///           });
+/// }
+/// ```
+pub trait WaitCondition<Marker> {
+	/// Returns the condition's current value, `delta_time` is the time passed since the previous poll.
+	fn check(&mut self, delta_time: f64) -> bool;
+}
+
+impl<F: FnMut() -> bool> WaitCondition<()> for F {
+	fn check(&mut self, _delta_time: f64) -> bool {
+		self()
+	}
+}
+
+impl<F: FnMut(f64) -> bool> WaitCondition<f64> for F {
+	fn check(&mut self, delta_time: f64) -> bool {
+		self(delta_time)
+	}
+}
+
+struct Condition<C, M> {
+	condition: C,
+	/// Whether the coroutine waits while the condition is true ([wait_while]) or while it's false ([wait_until]).
+	waits_while: bool,
+	marker: PhantomData<fn() -> M>,
+}
+
+impl<C: WaitCondition<M>, M> KeepWaiting for Condition<C, M> {
+	fn keep_waiting(&mut self, delta_time: f64) -> bool {
+		self.condition.check(delta_time) == self.waits_while
+	}
+
+	fn describe(&self) -> Option<String> {
+		if self.waits_while {
+			Some("waiting while a condition is true".to_string())
+		} else {
+			Some("waiting until a condition is true".to_string())
+		}
+	}
+}
+
+/// Coroutine resumes execution once `f` has returned true continuously for `seconds`.
+///
+/// `f` is invoked whenever the coroutine is polled, the time it has been true is accumulated from the poll's delta time
+/// (see [seconds] for how delta time depends on [PollMode]). Whenever `f` returns false, the accumulated time is reset.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::classes::RigidBody3D;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_until_stable(node: Gd<Node>, body: Gd<RigidBody3D>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield wait_until_stable(0.5, move || body.is_sleeping());
+///                godot_print!("Body has been sleeping for half a second! Resuming...");
+///           });
+/// }
+/// ```
+pub fn wait_until_stable(seconds: f64, f: impl FnMut() -> bool + 'static) -> SpireYield {
+	SpireYield::inline(UntilStable {
+		f,
+		required: seconds,
+		stable_for: 0.0,
+		started: false,
+	})
+}
+
+struct UntilStable<F> {
+	f: F,
+	required: f64,
+	stable_for: f64,
+	/// The first check happens on the same poll the coroutine yielded, whose delta time was spent before the wait started.
+	started: bool,
+}
+
+impl<F: FnMut() -> bool> KeepWaiting for UntilStable<F> {
+	fn keep_waiting(&mut self, delta_time: f64) -> bool {
+		if (self.f)() {
+			if self.started {
+				self.stable_for += delta_time;
+			}
+		} else {
+			self.stable_for = 0.0;
+		}
+
+		self.started = true;
+
+		self.stable_for < self.required
+	}
+
+	fn describe(&self) -> Option<String> {
+		Some(format!("waiting until a condition is true for {:.2} seconds ({:.2} so far)", self.required, self.stable_for))
+	}
+}
+
+/// Coroutine resumes execution once the value returned by `read` differs from its initial value.
+///
+/// The initial value is read when the coroutine starts waiting, then `read` is invoked whenever the coroutine is polled.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_for_change(node: Gd<Node>, player: Gd<Node2D>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield wait_for_change(move || player.get_position());
+///                godot_print!("Player moved! Resuming...");
+///           });
+/// }
+/// ```
+pub fn wait_for_change<T: PartialEq + 'static>(mut read: impl FnMut() -> T + 'static) -> SpireYield {
+	let mut initial = None;
+
+	SpireYield::inline(move || {
+		let current = read();
+
+		match &initial {
+			Some(initial) => *initial == current,
+			None => {
+				initial = Some(current);
+				true
+			}
+		}
+	})
+}
+
+/// Coroutine resumes execution once `callable` returns something other than `nil`.
+///
+/// `callable` is invoked (without arguments) whenever the coroutine is polled, starting on the poll it yielded.
+/// Also returns a cell that will contain the first non-nil value returned.
+///
+/// If `callable` becomes invalid (e.g. its object was freed), the coroutine resumes and the cell stays `None`.
+///
+/// Works with callables defined in GDScript, making it an adapter for "is it ready yet?" methods that return `null` until they have data.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_for_some(node: Gd<Node>, loader: Gd<Node>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                let (wait, level) = wait_for_some(Callable::from_object_method(&loader, "try_get_level"));
+///                yield wait;
+///                godot_print!("Level loaded: {:?}", level.borrow());
+///           });
+/// }
+/// ```
+pub fn wait_for_some(callable: Callable) -> (SpireYield, Rc<RefCell<Option<Variant>>>) {
+	let value = Rc::new(RefCell::new(None));
+
+	let wait = {
+		let value = value.clone();
+
+		SpireYield::inline(move || {
+			if !callable.is_valid() {
+				return false;
+			}
+
+			let result = callable.callv(&VariantArray::new());
+
+			if result.is_nil() {
+				true
+			} else {
+				*value.borrow_mut() = Some(result);
+				false
+			}
+		})
+	};
+
+	(wait, value)
+}
+
+/// Coroutine resumes execution on its first poll after a new [process](INode::process) frame started, 
+/// regardless of the coroutine's [PollMode].
+///
+/// Useful for coroutines polled on [physics_process](INode::physics_process) that need to wait until visuals
+/// were updated at least once.
+///
+/// Progress is tracked by comparing [Engine::get_process_frames] with a snapshot taken when this function is called.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_for_next_process_phase(node: Gd<Node>) {
+///      node.coroutine(
+///           #[coroutine] move || {
+///                godot_print!("Reading physics-settled positions...");
+///                yield wait_for_next_process_phase();
+///                godot_print!("Visuals were updated! Resuming...");
+///           })
+///          .poll_mode(PollMode::Physics)
+///          .spawn();
+/// }
+/// ```
+pub fn wait_for_next_process_phase() -> SpireYield {
+	let start = Engine::singleton().get_process_frames();
+	SpireYield::inline(move || Engine::singleton().get_process_frames() == start)
+}
+
+/// Coroutine resumes execution on its first poll after a new [physics_process](INode::physics_process) tick started, 
+/// regardless of the coroutine's [PollMode].
+///
+/// Useful for coroutines polled on [process](INode::process) that need physics to settle before reading positions/collisions.
+///
+/// Progress is tracked by comparing [Engine::get_physics_frames] with a snapshot taken when this function is called.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_wait_for_next_physics_phase(node: Gd<Node>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield wait_for_next_physics_phase();
+///                godot_print!("Physics ticked! Resuming...");
+///           });
+/// }
+/// ```
+pub fn wait_for_next_physics_phase() -> SpireYield {
+	let start = Engine::singleton().get_physics_frames();
+	SpireYield::inline(move || Engine::singleton().get_physics_frames() == start)
+}
+
+/// Moves the coroutine's node under `parent`, then resumes execution on the coroutine's next poll.
+///
+/// The coroutine keeps running, but in the context of its new parent, which matters because 
+/// [ProcessMode](godot::classes::node::ProcessMode) is inherited: e.g. moving under a subtree that processes while the game is paused.
+///
+/// Must be created from inside the coroutine's closure, otherwise logs an error and doesn't move anything.
+/// The move is deferred until the end of the frame, since the tree cannot be safely modified in the middle of a poll.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_reparent_to(node: Gd<Node>, cutscene_root: Gd<Node>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield reparent_to(cutscene_root);
+///                godot_print!("Now running under the cutscene's root, which keeps processing while paused.");
+///           });
+/// }
+/// ```
+pub fn reparent_to(parent: Gd<impl Inherits<Node>>) -> SpireYield {
+	let Some(mut coroutine) = SpireCoroutine::current()
+	else {
+		godot_error!("`reparent_to` must be called from inside a coroutine's closure.");
+		return frames(0);
+	};
+
+	coroutine.call_deferred("reparent", &[parent.upcast::<Node>().to_variant()]);
+	frames(1)
+}
+
+/// Emits [value](SIGNAL_VALUE) on the coroutine's node with `value` as the argument, then resumes execution on the coroutine's next poll.
+///
+/// Lets observers watch the progress of a coroutine that computes something incrementally, 
+/// without them having to share state with the closure.
+///
+/// Must be created from inside the coroutine's closure, otherwise logs an error and doesn't emit anything.
+/// The signal is emitted immediately, so handlers run before the closure parks.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::classes::ProgressBar;
+/// use godot::prelude::*;
+///
+/// fn showcase_yield_value(node: Gd<Node>, progress_bar: Gd<ProgressBar>) {
+///      let mut coroutine =
+///           node.start_coroutine(
+///                #[coroutine] move || {
+///                     for chunk in 0..100 {
+///                          // Generate a chunk of the level...
+///                          yield yield_value((chunk + 1) as f64);
+///                     }
+///                });
+///
+///      coroutine.connect(SIGNAL_VALUE, &Callable::from_local_fn("update_progress",
+///           move |args| {
+///                if let Ok(progress) = args[0].try_to::<f64>() {
+///                     progress_bar.clone().set_value(progress);
+///                }
+///
+///                Ok(Variant::nil())
+///           }));
+/// }
+/// ```
+pub fn yield_value(value: impl ToGodot) -> SpireYield {
+	let Some(mut coroutine) = SpireCoroutine::current()
+	else {
+		godot_error!("`yield_value` must be called from inside a coroutine's closure.");
+		return frames(1);
+	};
+
+	coroutine.emit_signal(SIGNAL_VALUE, &[value.to_variant()]);
+	frames(1)
 }
 
 /// Yield for a number of frames.
@@ -111,6 +980,14 @@ pub fn wait_until(mut f: impl FnMut() -> bool + 'static) -> SpireYield {
 /// A frame equals a single [process](INode::process) 
 /// or [physics_process](INode::physics_process)) call, depending on the coroutine's [PollMode].
 ///
+/// - `frames(0)` doesn't wait, the coroutine keeps executing in the same poll 
+///   (unless it's limited by [max_steps_per_frame](crate::prelude::CoroutineBuilder::max_steps_per_frame)).
+/// - Negative values are clamped to 0, debug builds also log a warning when the coroutine yields them.
+/// - There's no upper limit, `frames(i64::MAX)` waits forever in practice (until [skip_wait](SpireCoroutine::skip_wait), 
+///   [kill](SpireCoroutine::kill), etc.).
+/// - Long waits cost the same as short ones: each poll only compares the coroutine's [elapsed frames](SpireCoroutine::get_elapsed_frames)
+///   against the frame the wait ends on.
+///
 /// # Example
 ///
 /// ```no_run
@@ -136,10 +1013,15 @@ pub const fn frames(frames: i64) -> SpireYield {
 /// 
 /// The time counter is affected by [Engine::time_scale](Engine::get_time_scale)
 /// 
-/// The time counter is also dependent on the coroutine's [PollMode].
+/// The time counter is also dependent on the coroutine's [PollMode]:
+/// - [PollMode::Process]: each poll consumes the frame's `delta`.
+/// - [PollMode::Physics]: each poll consumes the fixed physics step (`1 / physics_ticks_per_second`), 
+///   which only matches wall-clock time while the engine keeps up with the physics tick rate.
 /// 
 /// Time does not pass if the coroutine's not being processed.
 ///
+/// For waits measured in wall-clock time regardless of poll mode, pause state or time scale, use [real_seconds].
+///
 /// # Example
 ///
 /// ```no_run
@@ -159,4 +1041,288 @@ pub const fn frames(frames: i64) -> SpireYield {
 /// ```
 pub const fn seconds(seconds: f64) -> SpireYield {
 	SpireYield::Seconds(seconds)
-}
\ No newline at end of file
+}
+
+/// A timed wait that is plain data: it can be copied, and stored in `const` and `static` items.
+///
+/// Useful for data tables of timings defined at compile time (e.g. animation frame schedules), 
+/// which can't hold [SpireYield] directly since its custom variants aren't `Sync`. 
+/// See [frame_steps], [second_steps] and [wait_sequence].
+///
+/// Converts into [SpireYield] with [into_yield](Self::into_yield) or [Into].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum WaitStep {
+	/// See [frames].
+	Frames(i64),
+	/// See [seconds].
+	Seconds(f64),
+}
+
+impl WaitStep {
+	/// Creates the equivalent [SpireYield].
+	pub const fn into_yield(self) -> SpireYield {
+		match self {
+			WaitStep::Frames(amount) => frames(amount),
+			WaitStep::Seconds(amount) => seconds(amount),
+		}
+	}
+}
+
+impl From<WaitStep> for SpireYield {
+	fn from(step: WaitStep) -> Self {
+		step.into_yield()
+	}
+}
+
+/// Creates a [WaitStep::Frames] for each amount in `frames`, can be used in `const` contexts.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// static BLINK_SCHEDULE: [WaitStep; 4] = frame_steps([10, 2, 10, 30]);
+///
+/// fn showcase_frame_steps(node: Gd<Node>, mut eyes: Gd<Node2D>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                for step in BLINK_SCHEDULE {
+///                     let visible = eyes.is_visible();
+///                     eyes.set_visible(!visible);
+///                     yield step.into();
+///                }
+///           });
+/// }
+/// ```
+pub const fn frame_steps<const N: usize>(frames: [i64; N]) -> [WaitStep; N] {
+	let mut steps = [WaitStep::Frames(0); N];
+	let mut index = 0;
+
+	while index < N {
+		steps[index] = WaitStep::Frames(frames[index]);
+		index += 1;
+	}
+
+	steps
+}
+
+/// Creates a [WaitStep::Seconds] for each amount in `seconds`, can be used in `const` contexts, see [frame_steps].
+pub const fn second_steps<const N: usize>(seconds: [f64; N]) -> [WaitStep; N] {
+	let mut steps = [WaitStep::Seconds(0.0); N];
+	let mut index = 0;
+
+	while index < N {
+		steps[index] = WaitStep::Seconds(seconds[index]);
+		index += 1;
+	}
+
+	steps
+}
+
+/// Coroutine resumes execution once it waited on each of `steps`, in order.
+///
+/// Behaves exactly like yielding each step in order, see [SpireYield::then_wait].
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// const INTRO_PAUSES: [WaitStep; 3] = second_steps([0.5, 1.0, 0.25]);
+///
+/// fn showcase_wait_sequence(node: Gd<Node>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield wait_sequence(&INTRO_PAUSES);
+///                godot_print!("Waited for 1.75 seconds in total! Resuming...");
+///           });
+/// }
+/// ```
+pub fn wait_sequence(steps: &[WaitStep]) -> SpireYield {
+	SpireYield::Dyn(Box::new(Sequence {
+		steps: steps.iter().map(|step| step.into_yield()).collect(),
+		unconsumed_delta: None,
+	}))
+}
+
+/// Yield for a specific amount of physics time, regardless of the coroutine's [PollMode].
+///
+/// Time advances by the fixed physics step (`1 / physics_ticks_per_second`) on every physics tick,
+/// which makes timers deterministic even for coroutines polled on [process](INode::process), whose delta time varies.
+///
+/// The wait is converted into an amount of physics ticks (rounded up) when this function is called,
+/// then it resumes on the coroutine's first poll after that many ticks passed, tracked through [Engine::get_physics_frames].
+///
+/// Like [real_seconds], and unlike [seconds], the ticks keep counting while the coroutine is [paused](SpireCoroutine::pause)
+/// (or the scene tree is), since the engine's physics frame counter doesn't stop. A coroutine paused for longer than the wait
+/// resumes on its first poll after being resumed.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_physics_seconds(node: Gd<Node>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield physics_seconds(0.5);
+///                godot_print!("30 physics ticks have passed (at 60 ticks per second)! Resuming...");
+///           });
+/// }
+/// ```
+pub fn physics_seconds(seconds: f64) -> SpireYield {
+	let engine = Engine::singleton();
+	let ticks = (seconds * engine.get_physics_ticks_per_second() as f64).ceil().max(0.0) as u64;
+	let deadline = engine.get_physics_frames() + ticks;
+
+	SpireYield::inline(move || Engine::singleton().get_physics_frames() < deadline)
+}
+
+/// Yield for a specific amount of wall-clock time, measured with [Time::get_ticks_usec].
+///
+/// Unlike [seconds], this ignores the coroutine's [PollMode], [Engine::time_scale](Engine::get_time_scale),
+/// and keeps counting while the coroutine is paused. The coroutine still only resumes when polled.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_real_seconds(node: Gd<Node>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                yield real_seconds(2.0);
+///                godot_print!("2 real seconds have passed! Resuming...");
+///           });
+/// }
+/// ```
+pub fn real_seconds(seconds: f64) -> SpireYield {
+	let deadline_usec = Time::singleton().get_ticks_usec() + (seconds * 1_000_000.0) as u64;
+	SpireYield::inline(move || Time::singleton().get_ticks_usec() < deadline_usec)
+}
+
+/// Yield for a specific amount of real seconds, not affected by [Engine::time_scale](Engine::get_time_scale).
+///
+/// Like [seconds], the time counter depends on the coroutine's [PollMode] and doesn't pass while the coroutine is paused,
+/// but each poll's delta time is divided by the engine's time scale, so the wait tracks real seconds even while the game is slowed down.
+///
+/// Reported by [remaining_seconds](SpireCoroutine::remaining_seconds) and [describe_wait](SpireCoroutine::describe_wait)
+/// in real seconds.
+/// Useful for UI animations that should keep their speed during slow-motion effects.
+///
+/// While the engine's time scale is 0, no time passes: the coroutine keeps waiting until the time scale is restored.
+///
+/// Only the engine's time scale is compensated, the coroutine's own [time_scale](CoroutineBuilder::time_scale) still applies.
+/// For waits measured in wall-clock time regardless of pause state, use [real_seconds].
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_seconds_unscaled(node: Gd<Node>, mut hit_flash: Gd<Node2D>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                hit_flash.set_visible(true);
+///                yield seconds_unscaled(0.5);
+///                hit_flash.set_visible(false);
+///           });
+/// }
+/// ```
+pub const fn seconds_unscaled(seconds: f64) -> SpireYield {
+	SpireYield::SecondsUnscaled(seconds)
+}
+
+/// Subtracts `delta_time` (in engine time) from the real seconds left in a [SpireYield::SecondsUnscaled] wait.
+///
+/// Returns the unconsumed delta time, in engine time, once the wait is over.
+/// While the engine's time scale is 0, no time passes.
+pub(crate) fn count_down_unscaled(remaining: &mut f64, delta_time: f64) -> Option<f64> {
+	let time_scale = Engine::singleton().get_time_scale();
+
+	if time_scale <= 0.0 {
+		return None;
+	}
+
+	*remaining -= delta_time / time_scale;
+
+	if *remaining > 0.0 {
+		None
+	} else {
+		// Converted back to scaled time, since that's what the coroutine passes on to the next wait.
+		Some(-*remaining * time_scale)
+	}
+}
+
+/// Yield for a random amount of engine time, between `min` and `max` seconds (inclusive).
+///
+/// The duration is sampled once, using Godot's global random number generator,
+/// the resulting yield behaves exactly like [seconds].
+///
+/// See [random_seconds_with] for a reproducible variant.
+///
+/// # Example
+///
+/// ```no_run
+/// #![feature(coroutines)]
+/// use gdext_coroutines::prelude::*;
+/// use godot::prelude::*;
+///
+/// fn showcase_random_seconds(node: Gd<Node>) {
+///      node.start_coroutine(
+///           #[coroutine] move || {
+///                for _ in 0..10 {
+///                     yield random_seconds(1.0, 3.0);
+///                     godot_print!("Spawning enemy!");
+///                }
+///           });
+/// }
+/// ```
+pub fn random_seconds(min: f64, max: f64) -> SpireYield {
+	let (min, max) = if min <= max { (min, max) } else { (max, min) };
+	seconds(godot::global::randf_range(min, max))
+}
+
+/// Yield for a random number of frames, between `min` and `max` (inclusive).
+///
+/// The amount is sampled once, using Godot's global random number generator,
+/// the resulting yield behaves exactly like [frames].
+///
+/// See [random_frames_with] for a reproducible variant.
+pub fn random_frames(min: i64, max: i64) -> SpireYield {
+	frames_in_range(min, max, godot::global::randf())
+}
+
+/// Same as [random_seconds], but samples from `rng`, which allows reproducing the durations by seeding it.
+pub fn random_seconds_with(rng: &mut Gd<RandomNumberGenerator>, min: f64, max: f64) -> SpireYield {
+	let (min, max) = if min <= max { (min, max) } else { (max, min) };
+	let t = rng.randf() as f64;
+	seconds(min + (max - min) * t)
+}
+
+/// Same as [random_frames], but samples from `rng`, which allows reproducing the amounts by seeding it.
+pub fn random_frames_with(rng: &mut Gd<RandomNumberGenerator>, min: i64, max: i64) -> SpireYield {
+	frames_in_range(min, max, rng.randf() as f64)
+}
+
+/// Maps `t`, sampled in `0.0..=1.0`, to a frame amount between `min` and `max` (inclusive, in any order).
+///
+/// Godot's `randi_range` works with 32-bit integers, so both [random_frames] and [random_frames_with] sample a float instead.
+fn frames_in_range(min: i64, max: i64, t: f64) -> SpireYield {
+	let (min, max) = if min <= max { (min, max) } else { (max, min) };
+
+	// Widened, since the span of extreme ranges (e.g. `i64::MIN..=i64::MAX`) doesn't fit in an `i64`.
+	let span = max as i128 - min as i128 + 1;
+	let offset = (span as f64 * t).floor() as i128;
+	frames((min as i128 + offset).min(max as i128) as i64)
+}